@@ -0,0 +1,69 @@
+/*!
+ * Derive macros for [`easy-imgui`](https://crates.io/crates/easy-imgui).
+ *
+ * Do not use this crate directly, enable the `derive` feature of `easy-imgui` instead.
+ */
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Derives `easy_imgui::ComboItem` for a fieldless enum.
+///
+/// The label of each variant is its name, unless it has a `#[combo(label = "...")]` attribute.
+#[proc_macro_derive(ComboItem, attributes(combo))]
+pub fn derive_combo_item(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    combo_item(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+fn combo_item(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "ComboItem can only be derived for enums",
+        ));
+    };
+    let mut variants = Vec::new();
+    let mut labels = Vec::new();
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "ComboItem variants cannot have fields",
+            ));
+        }
+        let mut label = variant.ident.to_string();
+        for attr in &variant.attrs {
+            if !attr.path().is_ident("combo") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("label") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    label = value.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("unknown combo attribute"))
+                }
+            })?;
+        }
+        variants.push(&variant.ident);
+        labels.push(label);
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::easy_imgui::ComboItem for #name #ty_generics #where_clause {
+            const ALL: &'static [Self] = &[#(Self::#variants),*];
+            fn label(&self) -> &'static str {
+                match *self {
+                    #(Self::#variants => #labels,)*
+                }
+            }
+        }
+    })
+}
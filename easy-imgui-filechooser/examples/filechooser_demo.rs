@@ -1,4 +1,12 @@
-use easy_imgui_window::{easy_imgui as imgui, winit, AppHandler, Application};
+use easy_imgui_window::{
+    easy_imgui as imgui,
+    easy_imgui_renderer::{
+        easy_imgui_opengl::{self as glr, GlContext},
+        glow::{self, HasContext},
+        Renderer,
+    },
+    winit, AppHandler, Application,
+};
 use winit::event_loop::EventLoop;
 
 use easy_imgui_filechooser as filechooser;
@@ -17,6 +25,46 @@ fn main() {
 struct App {
     of_atlas: filechooser::CustomAtlas,
     of: Option<filechooser::FileChooser>,
+    thumbnails: Thumbnails,
+}
+
+/// Creates OpenGL textures for the thumbnails.
+struct Thumbnails {
+    gl: GlContext,
+    textures: Vec<glr::Texture>,
+}
+
+impl filechooser::ThumbnailLoader for Thumbnails {
+    fn create_texture(&mut self, image: &image::RgbaImage) -> imgui::TextureId {
+        let tex = glr::Texture::generate(&self.gl).unwrap();
+        unsafe {
+            self.gl.bind_texture(glow::TEXTURE_2D, Some(tex.id()));
+            self.gl.tex_parameter_i32(
+                glow::TEXTURE_2D,
+                glow::TEXTURE_MIN_FILTER,
+                glow::LINEAR as i32,
+            );
+            self.gl.tex_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                glow::RGBA as i32,
+                image.width() as i32,
+                image.height() as i32,
+                0,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelUnpackData::Slice(Some(image.as_raw())),
+            );
+            self.gl.bind_texture(glow::TEXTURE_2D, None);
+        }
+        let id = Renderer::map_tex(tex.id());
+        self.textures.push(tex);
+        id
+    }
+    fn destroy_texture(&mut self, texture: imgui::TextureId) {
+        let id = Renderer::unmap_tex(texture);
+        self.textures.retain(|t| Some(t.id()) != id);
+    }
 }
 
 impl Application for App {
@@ -31,8 +79,10 @@ impl Application for App {
                 .set_current()
                 .set_allow_user_scaling(true);
         }
+        let gl = args.window.renderer().gl_context().clone();
         let mut of = filechooser::FileChooser::new();
-        of.add_flags(filechooser::Flags::SHOW_READ_ONLY);
+        of.add_flags(filechooser::Flags::SHOW_READ_ONLY | filechooser::Flags::SHOW_PLACES);
+        of.set_view(filechooser::View::Thumbnails);
         of.add_filter(filechooser::Filter {
             id: filechooser::FilterId(0),
             text: "Text".to_string(),
@@ -54,6 +104,10 @@ impl Application for App {
         App {
             of_atlas: Default::default(),
             of: Some(of),
+            thumbnails: Thumbnails {
+                gl,
+                textures: Vec::new(),
+            },
         }
     }
 }
@@ -63,19 +117,22 @@ impl imgui::UiBuilder for App {
     }
     fn do_ui(&mut self, ui: &imgui::Ui<Self>) {
         if ui.shortcut_ex(imgui::Key::F5, imgui::InputFlags::RouteGlobal) {
-            if self.of.is_none() {
+            if let Some(mut of) = self.of.take() {
+                of.release_thumbnails(&mut self.thumbnails);
+            } else {
                 let of = filechooser::FileChooser::new();
                 self.of = Some(of);
-            } else {
-                self.of = None;
             }
         }
 
         if let Some(of) = &mut self.of {
-            let res = of.do_ui(ui, &self.of_atlas);
+            let params = filechooser::UiParameters::new(&self.of_atlas)
+                .with_thumbnails(&mut self.thumbnails);
+            let res = of.do_ui(ui, params);
             match res {
                 filechooser::Output::Continue => {}
                 filechooser::Output::Cancel => {
+                    of.release_thumbnails(&mut self.thumbnails);
                     self.of = None;
                 }
                 filechooser::Output::Ok => {
@@ -85,6 +142,7 @@ impl imgui::UiBuilder for App {
                         _ => None,
                     };
                     let path = of.full_path(ext);
+                    of.release_thumbnails(&mut self.thumbnails);
                     dbg!(&self.of, path);
                     self.of = None;
                 }
@@ -11,6 +11,7 @@ use image::DynamicImage;
 use std::io::Result;
 use std::{
     borrow::Cow,
+    collections::HashMap,
     ffi::{OsStr, OsString},
     fs::DirEntry,
     path::{Path, PathBuf},
@@ -47,6 +48,8 @@ pub struct FileChooser {
     entries: Vec<FileEntry>,
     selected: Option<usize>,
     sort_dirty: bool,
+    // Last sort specs from the table, kept while navigating and used by the thumbnails view.
+    sort_specs: Vec<(usize, imgui::SortDirection)>,
     visible_dirty: bool,
     scroll_dirty: bool,
     show_hidden: bool,
@@ -59,8 +62,42 @@ pub struct FileChooser {
     read_only: bool,
     visible_entries: Vec<usize>,
     path_size_overflow: f32,
+    multiple: bool,
+    // Only used if `multiple`, full paths in selection order.
+    selected_paths: Vec<PathBuf>,
+    view: View,
+    thumbnail_size: f32,
+    // Thumbnails of the current directory, by file name. `None` if it is not an image.
+    thumbnails: HashMap<OsString, Option<Thumbnail>>,
+    // Textures no longer used, to be destroyed in the next frame.
+    stale_textures: Vec<imgui::TextureId>,
+    bookmarks: Vec<PathBuf>,
+    // Common locations, with their names. Computed the first time they are shown.
+    places: Option<Vec<(String, PathBuf)>>,
 }
 
+/// How the entries of the current directory are shown.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum View {
+    /// A table with the name, size and modification time of each entry.
+    #[default]
+    List,
+    /// A grid of thumbnails, with a preview of the image files.
+    ///
+    /// It requires a `ThumbnailLoader`, see [`UiParameters::with_thumbnails`].
+    /// Without it the `List` view is used.
+    Thumbnails,
+}
+
+#[derive(Copy, Clone)]
+struct Thumbnail {
+    texture: imgui::TextureId,
+    size: [u32; 2],
+}
+
+// The maximum size of the decoded thumbnails, they are scaled down when drawn.
+const THUMBNAIL_MAX_SIZE: u32 = 256;
+
 /// The output of calling `do_ui` each frame.
 pub enum Output {
     /// The widget is still opened.
@@ -175,6 +212,10 @@ mod os {
             None
         }
     }
+
+    pub fn home_dir() -> Option<PathBuf> {
+        std::env::var_os("USERPROFILE").map(PathBuf::from)
+    }
 }
 
 #[cfg(not(target_os = "windows"))]
@@ -197,6 +238,10 @@ mod os {
             None
         }
     }
+
+    pub fn home_dir() -> Option<PathBuf> {
+        std::env::var_os("HOME").map(PathBuf::from)
+    }
 }
 
 struct EnumSubdirs {
@@ -232,6 +277,8 @@ bitflags::bitflags! {
     pub struct Flags: u32 {
         /// Shows the "Read only" check.
         const SHOW_READ_ONLY = 1;
+        /// Shows the common locations, such as the home directory, in the bookmarks sidebar.
+        const SHOW_PLACES = 2;
     }
 }
 
@@ -250,6 +297,7 @@ impl FileChooser {
             entries: Vec::new(),
             selected: None,
             sort_dirty: false,
+            sort_specs: vec![(1, imgui::SortDirection::Ascending)],
             visible_dirty: false,
             scroll_dirty: false,
             show_hidden: false,
@@ -261,6 +309,14 @@ impl FileChooser {
             read_only: false,
             visible_entries: Vec::new(),
             path_size_overflow: 0.0,
+            multiple: false,
+            selected_paths: Vec::new(),
+            view: View::List,
+            thumbnail_size: 96.0,
+            thumbnails: HashMap::new(),
+            stale_textures: Vec::new(),
+            bookmarks: Vec::new(),
+            places: None,
         }
     }
     /// Adds the given option flags.
@@ -301,6 +357,8 @@ impl FileChooser {
         self.popup_dirs.clear();
         self.search_term.clear();
         self.path_size_overflow = 0.0;
+        self.selected_paths.clear();
+        self.clear_thumbnails();
         Ok(())
     }
 
@@ -360,6 +418,56 @@ impl FileChooser {
             Some(self.filters[self.active_filter_idx].id)
         }
     }
+    /// Enables or disables the selection of multiple files.
+    ///
+    /// When enabled, Ctrl+click toggles the selection of a file and Shift+click selects a range.
+    /// Directories are never selected, double-clicking them still navigates. The selection
+    /// is cleared when the current directory changes.
+    ///
+    /// By default it is disabled.
+    pub fn set_multiple(&mut self, multiple: bool) {
+        self.multiple = multiple;
+        self.selected_paths.clear();
+    }
+    /// Gets whether the selection of multiple files is enabled.
+    pub fn multiple(&self) -> bool {
+        self.multiple
+    }
+    /// Gets the full paths of the selected files, in the order they were selected.
+    ///
+    /// It is only used if the multiple selection is enabled, see [`FileChooser::set_multiple`].
+    /// With single selection use [`FileChooser::full_path`] instead.
+    pub fn selected_paths(&self) -> &[PathBuf] {
+        &self.selected_paths
+    }
+    /// Changes how the entries are shown.
+    ///
+    /// By default it is `View::List`.
+    pub fn set_view(&mut self, view: View) {
+        self.view = view;
+        self.scroll_dirty = true;
+    }
+    /// Gets how the entries are shown.
+    pub fn view(&self) -> View {
+        self.view
+    }
+    /// Releases all the thumbnail textures.
+    ///
+    /// Call this before dropping the `FileChooser` if you used the thumbnails view, or the
+    /// textures will leak.
+    pub fn release_thumbnails(&mut self, loader: &mut dyn ThumbnailLoader) {
+        self.clear_thumbnails();
+        for tex in self.stale_textures.drain(..) {
+            loader.destroy_texture(tex);
+        }
+    }
+    fn clear_thumbnails(&mut self) {
+        self.stale_textures.extend(
+            self.thumbnails
+                .drain()
+                .filter_map(|(_, thumb)| Some(thumb?.texture)),
+        );
+    }
     /// Gets the status of the read-only check box.
     /// If the SHOW_READ_ONLY flag is not specified, it will return `false`.
     pub fn read_only(&self) -> bool {
@@ -414,6 +522,53 @@ impl FileChooser {
         }
         self.visible_dirty = true;
     }
+    /// Adds a filter with a user-facing name and a list of glob patterns, such as
+    /// `add_filter_patterns("Images (*.png *.jpg)", &["*.png", "*.jpg"])`.
+    ///
+    /// If this is the first filter, an "All files" filter is added before it, and it is the
+    /// active one by default.
+    ///
+    /// The id of the new filter is its index in the filter list, that is returned.
+    pub fn add_filter_patterns(
+        &mut self,
+        name: impl Into<String>,
+        patterns: &[&str],
+    ) -> std::result::Result<FilterId, glob::PatternError> {
+        let globs = patterns
+            .iter()
+            .map(|p| Pattern::new(p))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        if self.filters.is_empty() {
+            self.add_filter(Filter {
+                id: FilterId(0),
+                text: tr!("All files"),
+                globs: Vec::new(),
+            });
+        }
+        let id = FilterId(self.filters.len() as i32);
+        self.add_filter(Filter {
+            id,
+            text: name.into(),
+            globs,
+        });
+        Ok(id)
+    }
+    /// Adds a directory to the bookmarks sidebar, persisting them is up to the caller.
+    pub fn add_bookmark(&mut self, path: impl Into<PathBuf>) {
+        let path = path.into();
+        if !self.bookmarks.contains(&path) {
+            self.bookmarks.push(path);
+        }
+    }
+    /// Removes a directory from the bookmarks sidebar.
+    pub fn remove_bookmark(&mut self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        self.bookmarks.retain(|b| b != path);
+    }
+    /// Gets the current bookmarks.
+    pub fn bookmarks(&self) -> &[PathBuf] {
+        &self.bookmarks
+    }
     /// Draws the widget in the current frame.
     ///
     /// `params` is a `UiParameters` value that contains additional parameters for the UI.
@@ -431,7 +586,21 @@ impl FileChooser {
             }
         }
 
-        let UiParameters { atlas, mut preview } = params.into();
+        let UiParameters {
+            atlas,
+            mut preview,
+            mut thumbnails,
+        } = params.into();
+        if let Some(loader) = thumbnails.as_deref_mut() {
+            for tex in self.stale_textures.drain(..) {
+                loader.destroy_texture(tex);
+            }
+        }
+        let view = if thumbnails.is_some() {
+            self.view
+        } else {
+            View::List
+        };
         let mut next_path = None;
         let mut output = Output::Continue;
 
@@ -554,13 +723,28 @@ impl FileChooser {
 
         ui.text(&tr!("Search"));
         ui.same_line();
-        ui.set_next_item_width(-ui.get_frame_height() - style.ItemSpacing.x);
+        let slider_width = if view == View::Thumbnails {
+            ui.get_font_size() * 8.0
+        } else {
+            0.0
+        };
+        ui.set_next_item_width(
+            -ui.get_frame_height() - style.ItemSpacing.x - slider_width - style.ItemSpacing.x,
+        );
         if ui
             .input_text_config(lbl_id(c"", c"Search"), &mut self.search_term)
             .build()
         {
             self.visible_dirty = true;
         }
+        if view == View::Thumbnails {
+            ui.same_line();
+            ui.set_next_item_width(slider_width);
+            ui.slider_float_config(lbl_id(c"", c"thumbnail_size"), &mut self.thumbnail_size)
+                .range(32.0, THUMBNAIL_MAX_SIZE as f32)
+                .display_format(imgui::FloatFormat::F(0))
+                .build();
+        }
 
         ui.same_line();
         ui.with_push(
@@ -584,154 +768,157 @@ impl FileChooser {
         // Two rows of full controls
         let reserve = 2.0 * ui.get_frame_height_with_spacing();
         let preview_width = preview.width();
-        ui.table_config(lbl("FileChooser"), 4)
-            .flags(
-                imgui::TableFlags::RowBg
-                    | imgui::TableFlags::ScrollY
-                    | imgui::TableFlags::Resizable
-                    | imgui::TableFlags::Sortable
-                    | imgui::TableFlags::SizingFixedFit,
-            )
-            .outer_size(imgui::Vector2::new(-preview_width, -reserve))
-            .with(|| {
-                let pad = ui.style().FramePadding;
-                ui.table_setup_column("", imgui::TableColumnFlags::None, 0.00, 0);
-                ui.table_setup_column(
-                    tr!("Name"),
-                    imgui::TableColumnFlags::WidthStretch | imgui::TableColumnFlags::DefaultSort,
-                    0.0,
-                    0,
-                );
-                ui.table_setup_column(
-                    tr!("Size"),
-                    imgui::TableColumnFlags::WidthFixed,
-                    ui.calc_text_size("999.9 GiB").x + 2.0 * pad.x,
-                    0,
-                );
-                ui.table_setup_column(
-                    tr!("Modified"),
-                    imgui::TableColumnFlags::WidthFixed,
-                    ui.calc_text_size("2024-12-31 23:59:59").x + 2.0 * pad.x,
-                    0,
-                );
-                ui.table_setup_scroll_freeze(0, 1);
-                ui.table_headers_row();
-
-                // First we sort the entries in-place, then we filter them into `visible_entries`.
-                // We could do it the other way around, and it might be more efficient some times,
-                // but it probably doesn't matter too much in practice.
-
-                ui.table_with_sort_specs_always(|dirty, specs| {
-                    if dirty || self.sort_dirty {
-                        self.sort_dirty = false;
-                        self.resort_entries(specs);
+        if self.flags.contains(Flags::SHOW_PLACES) || !self.bookmarks.is_empty() {
+            ui.child_config(lbl("bookmarks"))
+                .size(imgui::Vector2::new(ui.get_font_size() * 10.0, -reserve))
+                .child_flags(imgui::ChildFlags::Borders | imgui::ChildFlags::ResizeX)
+                .with(|| self.bookmarks_ui(ui, &mut next_path));
+            ui.same_line();
+        }
+        if let (View::Thumbnails, Some(loader)) = (view, thumbnails.as_deref_mut()) {
+            ui.child_config(lbl("thumbnails"))
+                .size(imgui::Vector2::new(-preview_width, -reserve))
+                .with(|| self.thumbnails_ui(ui, atlas, loader, &mut next_path, &mut output));
+        } else {
+            ui.table_config(lbl("FileChooser"), 4)
+                .flags(
+                    imgui::TableFlags::RowBg
+                        | imgui::TableFlags::ScrollY
+                        | imgui::TableFlags::Resizable
+                        | imgui::TableFlags::Sortable
+                        | imgui::TableFlags::SizingFixedFit,
+                )
+                .outer_size(imgui::Vector2::new(-preview_width, -reserve))
+                .with(|| {
+                    let pad = ui.style().FramePadding;
+                    ui.table_setup_column("", imgui::TableColumnFlags::None, 0.00, 0);
+                    ui.table_setup_column(
+                        tr!("Name"),
+                        imgui::TableColumnFlags::WidthStretch
+                            | imgui::TableColumnFlags::DefaultSort,
+                        0.0,
+                        0,
+                    );
+                    ui.table_setup_column(
+                        tr!("Size"),
+                        imgui::TableColumnFlags::WidthFixed,
+                        ui.calc_text_size("999.9 GiB").x + 2.0 * pad.x,
+                        0,
+                    );
+                    ui.table_setup_column(
+                        tr!("Modified"),
+                        imgui::TableColumnFlags::WidthFixed,
+                        ui.calc_text_size("2024-12-31 23:59:59").x + 2.0 * pad.x,
+                        0,
+                    );
+                    ui.table_setup_scroll_freeze(0, 1);
+                    ui.table_headers_row();
+
+                    // First we sort the entries in-place, then we filter them into `visible_entries`.
+                    // We could do it the other way around, and it might be more efficient some times,
+                    // but it probably doesn't matter too much in practice.
+
+                    ui.table_with_sort_specs_always(|dirty, specs| {
+                        if dirty {
+                            self.sort_specs = specs
+                                .iter()
+                                .map(|s| (s.index(), s.sort_direction()))
+                                .collect();
+                        }
+                        if dirty || self.sort_dirty {
+                            self.sort_dirty = false;
+                            let specs = std::mem::take(&mut self.sort_specs);
+                            self.resort_entries(&specs);
+                            self.sort_specs = specs;
+                        }
+                        false
+                    });
+                    if self.visible_dirty {
+                        self.visible_dirty = false;
+                        self.scroll_dirty = true;
+                        self.recompute_visible_entries();
                     }
-                    false
-                });
-                if self.visible_dirty {
-                    self.visible_dirty = false;
-                    self.scroll_dirty = true;
-                    self.recompute_visible_entries();
-                }
 
-                let mut clipper = ui.list_clipper(self.visible_entries.len());
-                // If `scroll_dirty` we have to move the scroll to the "best" place.
-                // If there is a selected item, that is the best one, so it has to be added to the
-                // clipper, or it will be skipped.
-                if let (Some(i_sel), true) = (self.selected, self.scroll_dirty) {
-                    if let Some(idx) = self.visible_entries.iter().position(|i| *i == i_sel) {
-                        clipper.add_included_range(idx..idx + 1);
+                    let mut clipper = ui.list_clipper(self.visible_entries.len());
+                    // If `scroll_dirty` we have to move the scroll to the "best" place.
+                    // If there is a selected item, that is the best one, so it has to be added to the
+                    // clipper, or it will be skipped.
+                    if let (Some(i_sel), true) = (self.selected, self.scroll_dirty) {
+                        if let Some(idx) = self.visible_entries.iter().position(|i| *i == i_sel) {
+                            clipper.add_included_range(idx..idx + 1);
+                        }
                     }
-                }
-                clipper.with(|i| {
-                    let i_entry = self.visible_entries[i];
-                    let entry = &self.entries[i_entry];
-
-                    ui.table_next_row(imgui::TableRowFlags::None, 0.0);
+                    clipper.with(|i| {
+                        let i_entry = self.visible_entries[i];
+                        let entry = &self.entries[i_entry];
+
+                        ui.table_next_row(imgui::TableRowFlags::None, 0.0);
+
+                        // File type
+                        ui.table_set_column_index(0);
+                        let icon_rr = match entry.kind {
+                            FileEntryKind::Parent => Some(atlas.parent_rr),
+                            FileEntryKind::Directory => Some(atlas.folder_rr),
+                            FileEntryKind::File => Some(atlas.file_rr),
+                            FileEntryKind::Root => Some(atlas.mypc_rr),
+                        };
+                        if let Some(rr) = icon_rr {
+                            let avail = ui.get_content_region_avail();
+                            let scale = ui.get_font_size() / 16.0;
+                            let img_w = ui.font_atlas().get_custom_rect(rr).Width as f32;
+                            ui.set_cursor_pos_x(
+                                ui.get_cursor_pos_x() + (avail.x - scale * img_w) / 2.0,
+                            );
+                            ui.image_with_custom_rect_config(rr, scale).build();
+                        }
 
-                    // File type
-                    ui.table_set_column_index(0);
-                    let icon_rr = match entry.kind {
-                        FileEntryKind::Parent => Some(atlas.parent_rr),
-                        FileEntryKind::Directory => Some(atlas.folder_rr),
-                        FileEntryKind::File => Some(atlas.file_rr),
-                        FileEntryKind::Root => Some(atlas.mypc_rr),
-                    };
-                    if let Some(rr) = icon_rr {
-                        let avail = ui.get_content_region_avail();
-                        let scale = ui.get_font_size() / 16.0;
-                        let img_w = ui.font_atlas().get_custom_rect(rr).Width as f32;
-                        ui.set_cursor_pos_x(
-                            ui.get_cursor_pos_x() + (avail.x - scale * img_w) / 2.0,
-                        );
-                        ui.image_with_custom_rect_config(rr, scale).build();
-                    }
+                        // File name
+                        ui.table_set_column_index(1);
+                        let is_selected = self.is_entry_selected(i_entry);
+                        if ui
+                            .selectable_config(entry.name.to_string_lossy().into())
+                            .flags(
+                                imgui::SelectableFlags::SpanAllColumns
+                                    | imgui::SelectableFlags::AllowOverlap
+                                    | imgui::SelectableFlags::AllowDoubleClick,
+                            )
+                            .selected(is_selected)
+                            .build()
+                        {
+                            self.click_entry(ui, i, &mut next_path, &mut output);
+                        }
 
-                    // File name
-                    ui.table_set_column_index(1);
-                    let is_selected = Some(i_entry) == self.selected;
-                    if ui
-                        .selectable_config(entry.name.to_string_lossy().into())
-                        .flags(
-                            imgui::SelectableFlags::SpanAllColumns
-                                | imgui::SelectableFlags::AllowOverlap
-                                | imgui::SelectableFlags::AllowDoubleClick,
-                        )
-                        .selected(is_selected)
-                        .build()
-                    {
-                        // Change the selected file
-                        self.selected = Some(i_entry);
-                        // Copy the selected name to `file_name`. Only regular files, no
-                        // directories.
-                        if entry.kind == FileEntryKind::File {
-                            self.file_name = entry.name.clone();
+                        if is_selected && self.scroll_dirty {
+                            self.scroll_dirty = false;
+                            ui.set_scroll_here_y(0.5);
                         }
-                        // If double click, confirm the widget.
-                        if ui.is_mouse_double_clicked(easy_imgui::MouseButton::Left) {
-                            match entry.kind {
-                                FileEntryKind::Parent => {
-                                    next_path = self.path.parent().map(|p| p.to_owned());
-                                }
-                                FileEntryKind::Directory | FileEntryKind::Root => {
-                                    next_path = Some(self.path.join(&entry.name));
-                                }
-                                FileEntryKind::File => {
-                                    output = Output::Ok;
-                                }
-                            }
+                        let entry = &self.entries[i_entry];
+
+                        // File size
+                        ui.table_set_column_index(2);
+                        if let Some(size) = entry.size {
+                            let text = format!("{}", ByteSize(size));
+                            ui.text(&text);
                         }
-                    }
 
-                    if is_selected && self.scroll_dirty {
+                        // File modification time
+                        ui.table_set_column_index(3);
+                        if let Some(modified) = entry.modified {
+                            let tm = time::OffsetDateTime::from(modified);
+                            let s = tm
+                                .format(format_description!(
+                                    "[year]-[month]-[day] [hour]:[minute]:[second]"
+                                ))
+                                .unwrap_or_default();
+                            ui.text(&s);
+                        }
+                    });
+                    if self.scroll_dirty {
                         self.scroll_dirty = false;
-                        ui.set_scroll_here_y(0.5);
-                    }
-
-                    // File size
-                    ui.table_set_column_index(2);
-                    if let Some(size) = entry.size {
-                        let text = format!("{}", ByteSize(size));
-                        ui.text(&text);
-                    }
-
-                    // File modification time
-                    ui.table_set_column_index(3);
-                    if let Some(modified) = entry.modified {
-                        let tm = time::OffsetDateTime::from(modified);
-                        let s = tm
-                            .format(format_description!(
-                                "[year]-[month]-[day] [hour]:[minute]:[second]"
-                            ))
-                            .unwrap_or_default();
-                        ui.text(&s);
+                        ui.set_scroll_y(0.0);
                     }
                 });
-                if self.scroll_dirty {
-                    self.scroll_dirty = false;
-                    ui.set_scroll_y(0.0);
-                }
-            });
+        }
         if preview_width > 0.0 {
             ui.same_line();
             ui.child_config(lbl("preview"))
@@ -813,7 +1000,280 @@ impl FileChooser {
 
         output
     }
-    fn resort_entries(&mut self, specs: &[easy_imgui::TableColumnSortSpec]) {
+    fn bookmarks_ui<A>(&mut self, ui: &imgui::Ui<A>, next_path: &mut Option<PathBuf>) {
+        let places: &[(String, PathBuf)] = if self.flags.contains(Flags::SHOW_PLACES) {
+            self.places.get_or_insert_with(|| {
+                let mut places = Vec::new();
+                if let Some(home) = os::home_dir() {
+                    let documents = home.join("Documents");
+                    places.push((tr!("Home"), home));
+                    if documents.is_dir() {
+                        places.push((tr!("Documents"), documents));
+                    }
+                }
+                places
+            })
+        } else {
+            &[]
+        };
+        let bookmarks = self.bookmarks.iter().map(|b| {
+            let name = b.file_name().unwrap_or_else(|| b.as_os_str());
+            (name.to_string_lossy().into_owned(), b)
+        });
+        let places = places.iter().map(|(name, p)| (name.clone(), p));
+        for (i, (name, path)) in places.chain(bookmarks).enumerate() {
+            ui.with_push(imgui::ItemId(i), || {
+                if ui
+                    .selectable_config(lbl(name))
+                    .selected(*path == self.path)
+                    .build()
+                {
+                    *next_path = Some(path.clone());
+                }
+                ui.set_item_tooltip(path.display().to_string());
+            });
+        }
+    }
+    fn thumbnails_ui<A>(
+        &mut self,
+        ui: &imgui::Ui<A>,
+        atlas: &CustomAtlas,
+        loader: &mut dyn ThumbnailLoader,
+        next_path: &mut Option<PathBuf>,
+        output: &mut Output,
+    ) {
+        // There are no table headers here, so the last order from the list view is used
+        if self.sort_dirty {
+            self.sort_dirty = false;
+            let specs = std::mem::take(&mut self.sort_specs);
+            self.resort_entries(&specs);
+            self.sort_specs = specs;
+        }
+        if self.visible_dirty {
+            self.visible_dirty = false;
+            self.scroll_dirty = true;
+            self.recompute_visible_entries();
+        }
+        if self.scroll_dirty {
+            self.scroll_dirty = false;
+            ui.set_scroll_y(0.0);
+        }
+
+        let style = ui.style();
+        let cell = imgui::Vector2::new(
+            self.thumbnail_size,
+            self.thumbnail_size + ui.get_text_line_height_with_spacing(),
+        );
+        let avail = ui.get_content_region_avail().x;
+        let columns =
+            (((avail + style.ItemSpacing.x) / (cell.x + style.ItemSpacing.x)) as usize).max(1);
+        let rows = self.visible_entries.len().div_ceil(columns);
+        // Decoding images is slow, so only one new thumbnail is loaded per frame
+        let mut load_budget = 1;
+
+        ui.list_clipper(rows).with(|row| {
+            for col in 0..columns {
+                let i = row * columns + col;
+                let Some(&i_entry) = self.visible_entries.get(i) else {
+                    break;
+                };
+                if col > 0 {
+                    ui.same_line();
+                }
+                let pos = ui.get_cursor_screen_pos();
+                let entry = &self.entries[i_entry];
+                if ui
+                    .selectable_config(lbl_id(c"", entry.name.to_string_lossy()))
+                    .flags(imgui::SelectableFlags::AllowDoubleClick)
+                    .selected(self.is_entry_selected(i_entry))
+                    .size(cell)
+                    .build()
+                {
+                    self.click_entry(ui, i, next_path, output);
+                }
+                if ui.is_item_visible() {
+                    self.draw_thumbnail(ui, atlas, loader, &mut load_budget, i_entry, pos, cell);
+                }
+            }
+        });
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn draw_thumbnail<A>(
+        &mut self,
+        ui: &imgui::Ui<A>,
+        atlas: &CustomAtlas,
+        loader: &mut dyn ThumbnailLoader,
+        load_budget: &mut usize,
+        i_entry: usize,
+        pos: imgui::Vector2,
+        cell: imgui::Vector2,
+    ) {
+        let entry = &self.entries[i_entry];
+        let mut thumb = self.thumbnails.get(&entry.name).copied();
+        if thumb.is_none() && entry.kind == FileEntryKind::File && *load_budget > 0 {
+            *load_budget -= 1;
+            let path = self.path.join(&entry.name);
+            // Only files with a known image extension are decoded
+            let loaded = image::ImageFormat::from_path(&path)
+                .ok()
+                .and_then(|_| image::open(&path).ok())
+                .map(|img| {
+                    let img = img
+                        .thumbnail(THUMBNAIL_MAX_SIZE, THUMBNAIL_MAX_SIZE)
+                        .to_rgba8();
+                    Thumbnail {
+                        texture: loader.create_texture(&img),
+                        size: [img.width(), img.height()],
+                    }
+                });
+            self.thumbnails.insert(entry.name.clone(), loaded);
+            thumb = Some(loaded);
+        }
+
+        let dl = ui.window_draw_list();
+        let side = cell.x;
+        match thumb.flatten() {
+            Some(thumb) => {
+                let scale = side / thumb.size[0].max(thumb.size[1]) as f32;
+                let size =
+                    imgui::Vector2::new(thumb.size[0] as f32 * scale, thumb.size[1] as f32 * scale);
+                let min = pos + (imgui::Vector2::new(side, side) - size) / 2.0;
+                dl.add_image(
+                    thumb.texture,
+                    min,
+                    min + size,
+                    imgui::Vector2::new(0.0, 0.0),
+                    imgui::Vector2::new(1.0, 1.0),
+                    imgui::Color::WHITE,
+                );
+            }
+            None => {
+                let rr = match entry.kind {
+                    FileEntryKind::Parent => atlas.parent_rr,
+                    FileEntryKind::Directory => atlas.folder_rr,
+                    FileEntryKind::File => atlas.file_rr,
+                    FileEntryKind::Root => atlas.mypc_rr,
+                };
+                let font_atlas = ui.font_atlas();
+                let rect = font_atlas.get_custom_rect(rr);
+                let [tex_w, tex_h] = font_atlas.texture_size();
+                let uv0 =
+                    imgui::Vector2::new(rect.X as f32 / tex_w as f32, rect.Y as f32 / tex_h as f32);
+                let uv1 = imgui::Vector2::new(
+                    (rect.X + rect.Width) as f32 / tex_w as f32,
+                    (rect.Y + rect.Height) as f32 / tex_h as f32,
+                );
+                let size = imgui::Vector2::new(side / 2.0, side / 2.0);
+                let min = pos + (imgui::Vector2::new(side, side) - size) / 2.0;
+                dl.add_image(
+                    font_atlas.texture_id(),
+                    min,
+                    min + size,
+                    uv0,
+                    uv1,
+                    imgui::Color::WHITE,
+                );
+            }
+        }
+
+        // The name, shortened to fit the cell
+        let mut name = entry.name.to_string_lossy().into_owned();
+        if ui.calc_text_size(&name).x > side {
+            while !name.is_empty() && ui.calc_text_size(&format!("{name}...")).x > side {
+                name.pop();
+            }
+            name.push_str("...");
+        }
+        let text_width = ui.calc_text_size(&name).x;
+        dl.add_text(
+            pos + imgui::Vector2::new((side - text_width).max(0.0) / 2.0, side),
+            ui.style().color(imgui::ColorId::Text),
+            &name,
+        );
+    }
+    fn is_entry_selected(&self, i_entry: usize) -> bool {
+        if self.multiple {
+            let entry = &self.entries[i_entry];
+            entry.kind == FileEntryKind::File
+                && self.selected_paths.contains(&self.path.join(&entry.name))
+        } else {
+            Some(i_entry) == self.selected
+        }
+    }
+    // `i` is the index in `visible_entries` of the clicked entry.
+    fn click_entry<A>(
+        &mut self,
+        ui: &imgui::Ui<A>,
+        i: usize,
+        next_path: &mut Option<PathBuf>,
+        output: &mut Output,
+    ) {
+        let i_entry = self.visible_entries[i];
+        if self.multiple && self.entries[i_entry].kind == FileEntryKind::File {
+            let io = ui.io();
+            self.update_multiple_selection(i, io.KeyCtrl, io.KeyShift);
+        }
+        let entry = &self.entries[i_entry];
+        // Change the selected file
+        self.selected = Some(i_entry);
+        // Copy the selected name to `file_name`. Only regular files, no
+        // directories.
+        if entry.kind == FileEntryKind::File {
+            self.file_name = entry.name.clone();
+        }
+        // If double click, confirm the widget.
+        if ui.is_mouse_double_clicked(easy_imgui::MouseButton::Left) {
+            match entry.kind {
+                FileEntryKind::Parent => {
+                    *next_path = self.path.parent().map(|p| p.to_owned());
+                }
+                FileEntryKind::Directory | FileEntryKind::Root => {
+                    *next_path = Some(self.path.join(&entry.name));
+                }
+                FileEntryKind::File => {
+                    *output = Output::Ok;
+                }
+            }
+        }
+    }
+    // `i` is the index in `visible_entries` of the clicked entry, that is a file.
+    fn update_multiple_selection(&mut self, i: usize, ctrl: bool, shift: bool) {
+        let path = self.path.join(&self.entries[self.visible_entries[i]].name);
+        let anchor = self
+            .selected
+            .and_then(|i_sel| self.visible_entries.iter().position(|i| *i == i_sel));
+        match (ctrl, shift, anchor) {
+            (_, true, Some(anchor)) => {
+                if !ctrl {
+                    self.selected_paths.clear();
+                }
+                let range = if anchor <= i { anchor..=i } else { i..=anchor };
+                for &i_entry in &self.visible_entries[range] {
+                    let entry = &self.entries[i_entry];
+                    if entry.kind != FileEntryKind::File {
+                        continue;
+                    }
+                    let p = self.path.join(&entry.name);
+                    if !self.selected_paths.contains(&p) {
+                        self.selected_paths.push(p);
+                    }
+                }
+            }
+            (true, _, _) => {
+                if let Some(pos) = self.selected_paths.iter().position(|p| *p == path) {
+                    self.selected_paths.remove(pos);
+                } else {
+                    self.selected_paths.push(path);
+                }
+            }
+            _ => {
+                self.selected_paths.clear();
+                self.selected_paths.push(path);
+            }
+        }
+    }
+    // `specs` are pairs of column index and sort direction, as in the table sort specs.
+    fn resort_entries(&mut self, specs: &[(usize, imgui::SortDirection)]) {
         let sel = self.selected.map(|i| self.entries[i].name.clone());
 
         self.entries.sort_by(|a, b| {
@@ -826,15 +1286,15 @@ impl FileChooser {
                 (_, Parent) => return Ordering::Greater,
                 (_, _) => (),
             }
-            for s in specs {
-                let res = match s.index() {
+            for &(index, direction) in specs {
+                let res = match index {
                     0 => a.kind.cmp(&b.kind),
                     1 => a.name.cmp(&b.name),
                     2 => a.size.cmp(&b.size),
                     3 => a.modified.cmp(&b.modified),
                     _ => continue,
                 };
-                let res = match s.sort_direction() {
+                let res = match direction {
                     easy_imgui::SortDirection::Ascending => res,
                     easy_imgui::SortDirection::Descending => res.reverse(),
                     _ => continue,
@@ -890,6 +1350,7 @@ impl FileChooser {
 pub struct UiParameters<'a, Preview> {
     atlas: &'a CustomAtlas,
     preview: Preview,
+    thumbnails: Option<&'a mut dyn ThumbnailLoader>,
 }
 
 /// A trait to build the "preview" section of the UI.
@@ -900,6 +1361,16 @@ pub trait PreviewBuilder<A> {
     fn do_ui(&mut self, ui: &imgui::Ui<A>, chooser: &FileChooser);
 }
 
+/// A trait to create the textures of the thumbnails, for [`View::Thumbnails`].
+///
+/// The `FileChooser` decodes the images, but it does not know how to create textures.
+pub trait ThumbnailLoader {
+    /// Creates a texture with the given image.
+    fn create_texture(&mut self, image: &image::RgbaImage) -> imgui::TextureId;
+    /// Destroys a texture created by `create_texture`, that is no longer used.
+    fn destroy_texture(&mut self, texture: imgui::TextureId);
+}
+
 /// A dummy implementation for `PreviewBuilder` that does nothing.
 pub struct NoPreview;
 
@@ -916,6 +1387,7 @@ impl<'a> UiParameters<'a, NoPreview> {
         UiParameters {
             atlas,
             preview: NoPreview,
+            thumbnails: None,
         }
     }
     /// Adds a preview object to this `UiParameters`.
@@ -923,6 +1395,17 @@ impl<'a> UiParameters<'a, NoPreview> {
         UiParameters {
             atlas: self.atlas,
             preview,
+            thumbnails: self.thumbnails,
+        }
+    }
+}
+
+impl<'a, Preview> UiParameters<'a, Preview> {
+    /// Adds a thumbnail loader to this `UiParameters`, required by [`View::Thumbnails`].
+    pub fn with_thumbnails(self, loader: &'a mut dyn ThumbnailLoader) -> Self {
+        UiParameters {
+            thumbnails: Some(loader),
+            ..self
         }
     }
 }
@@ -0,0 +1,178 @@
+use crate::glow::{self, HasContext};
+use crate::Renderer;
+use anyhow::{anyhow, Result};
+use easy_imgui as imgui;
+use easy_imgui_opengl as glr;
+#[cfg(feature = "headless")]
+use glutin::{
+    api::egl,
+    config::{ConfigSurfaceTypes, ConfigTemplateBuilder},
+    context::ContextAttributesBuilder,
+    prelude::*,
+};
+use imgui::image::RgbaImage;
+
+/// A renderer that draws into an offscreen framebuffer, instead of a window.
+///
+/// It is useful for taking snapshots of a UI, for example to compare them in automated tests.
+///
+/// With `HeadlessRenderer::new`, available with the `headless` feature, it creates its own
+/// surfaceless EGL context, so it needs no OS window at all. With
+/// [`HeadlessRenderer::with_gl_context`] it uses any current OpenGL context, such as a hidden
+/// window: the default framebuffer is never touched.
+pub struct HeadlessRenderer {
+    renderer: Renderer,
+    fbo: glr::Framebuffer,
+    _rbo: glr::Renderbuffer,
+    width: u32,
+    height: u32,
+    // Declared last, so that it is dropped after all the OpenGL objects
+    #[cfg(feature = "headless")]
+    egl_context: Option<egl::context::PossiblyCurrentContext>,
+}
+
+impl HeadlessRenderer {
+    /// Creates a new headless renderer, with an image size of `width` x `height` pixels.
+    ///
+    /// It creates a surfaceless EGL context on the first available device, and makes it current.
+    /// It fails if there is no EGL implementation with support for surfaceless contexts.
+    ///
+    /// The UI size is the same as the image size, with a scale factor of 1.
+    #[cfg(feature = "headless")]
+    pub fn new(width: u32, height: u32) -> Result<HeadlessRenderer> {
+        let device = egl::device::Device::query_devices()?
+            .next()
+            .ok_or_else(|| anyhow!("no EGL device found"))?;
+        let display = unsafe { egl::display::Display::with_device(&device, None)? };
+        let template = ConfigTemplateBuilder::new()
+            .with_surface_type(ConfigSurfaceTypes::empty())
+            .with_depth_size(0)
+            .with_stencil_size(0)
+            .build();
+        let config = unsafe { GlDisplay::find_configs(&display, template)? }
+            .next()
+            .ok_or_else(|| anyhow!("no EGL config found"))?;
+        let context_attributes = ContextAttributesBuilder::new().build(None);
+        let egl_context =
+            unsafe { GlDisplay::create_context(&display, &config, &context_attributes)? }
+                .make_current_surfaceless()?;
+        let gl =
+            unsafe { glow::Context::from_loader_function_cstr(|s| display.get_proc_address(s)) };
+        let mut headless = HeadlessRenderer::with_gl_context(std::rc::Rc::new(gl), width, height)?;
+        headless.egl_context = Some(egl_context);
+        Ok(headless)
+    }
+    /// Creates a new headless renderer, using an existing OpenGL context.
+    ///
+    /// The context must be current when calling any function of this object, including `drop`.
+    pub fn with_gl_context(
+        gl: glr::GlContext,
+        width: u32,
+        height: u32,
+    ) -> Result<HeadlessRenderer> {
+        let fbo = glr::Framebuffer::generate(&gl)?;
+        let rbo = glr::Renderbuffer::generate(&gl)?;
+        unsafe {
+            let rb_binder = glr::BinderRenderbuffer::bind(&rbo);
+            gl.renderbuffer_storage(rb_binder.target(), glow::RGBA8, width as i32, height as i32);
+            let fb_binder = glr::BinderFramebuffer::bind(&fbo);
+            gl.framebuffer_renderbuffer(
+                fb_binder.target(),
+                glow::COLOR_ATTACHMENT0,
+                rb_binder.target(),
+                Some(rbo.id()),
+            );
+            let status = gl.check_framebuffer_status(fb_binder.target());
+            if status != glow::FRAMEBUFFER_COMPLETE {
+                anyhow::bail!("incomplete framebuffer: {status:#x}");
+            }
+        }
+        let mut renderer = Renderer::new(gl)?;
+        renderer.set_size(imgui::Vector2::new(width as f32, height as f32), 1.0);
+        Ok(HeadlessRenderer {
+            renderer,
+            fbo,
+            _rbo: rbo,
+            width,
+            height,
+            #[cfg(feature = "headless")]
+            egl_context: None,
+        })
+    }
+    /// Gets the inner renderer, to change its options.
+    pub fn renderer(&mut self) -> &mut Renderer {
+        &mut self.renderer
+    }
+    /// Builds and renders a UI frame, using the `app` [`easy_imgui::UiBuilder`], and returns the resulting image.
+    ///
+    /// If this renderer owns its EGL context, it is made current first.
+    ///
+    /// Note that some widgets need a few frames to settle down, such as auto-resizing windows.
+    pub fn render<A: imgui::UiBuilder>(&mut self, app: &mut A) -> Result<RgbaImage> {
+        #[cfg(feature = "headless")]
+        if let Some(egl_context) = &self.egl_context {
+            egl_context.make_current_surfaceless()?;
+        }
+        let gl = self.renderer.gl_context().clone();
+        let _fb_binder = glr::BinderFramebuffer::bind(&self.fbo);
+        let _viewport = glr::PushViewport::new(&gl);
+        self.renderer.do_frame(app);
+
+        let mut pixels = vec![0; 4 * self.width as usize * self.height as usize];
+        unsafe {
+            gl.pixel_store_i32(glow::PACK_ALIGNMENT, 1);
+            gl.read_pixels(
+                0,
+                0,
+                self.width as i32,
+                self.height as i32,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelPackData::Slice(Some(&mut pixels)),
+            );
+        }
+        let mut image = RgbaImage::from_raw(self.width, self.height, pixels)
+            .ok_or_else(|| anyhow!("wrong image size"))?;
+        // OpenGL images are bottom-up
+        imgui::image::imageops::flip_vertical_in_place(&mut image);
+        Ok(image)
+    }
+}
+
+#[cfg(all(test, feature = "headless"))]
+mod tests {
+    use super::*;
+    use imgui::{lbl, vec2, Color, ColorId, Cond, WindowFlags};
+
+    struct ButtonApp;
+
+    impl imgui::UiBuilder for ButtonApp {
+        fn do_ui(&mut self, ui: &imgui::Ui<Self>) {
+            ui.set_next_window_pos(vec2(0.0, 0.0), Cond::Always, vec2(0.0, 0.0));
+            ui.set_next_window_size(vec2(200.0, 100.0), Cond::Always);
+            ui.window_config(lbl(c"test"))
+                .flags(WindowFlags::NoDecoration)
+                .with(|| {
+                    ui.with_push((ColorId::Button, Color::RED), || {
+                        ui.button_config(lbl(c"##red"))
+                            .size(vec2(100.0, 50.0))
+                            .build();
+                    });
+                });
+        }
+    }
+
+    #[test]
+    #[ignore = "needs an EGL implementation, run it with `cargo test --features headless -- --ignored`"]
+    fn button_is_rendered() {
+        let mut headless = HeadlessRenderer::new(200, 100).unwrap();
+        // The first frame builds the font atlas and settles the window
+        headless.render(&mut ButtonApp).unwrap();
+        let image = headless.render(&mut ButtonApp).unwrap();
+        assert_eq!(image.dimensions(), (200, 100));
+        // The button is at the window padding, so its center is well inside it
+        assert_eq!(image.get_pixel(58, 33).0, [255, 0, 0, 255]);
+        // And the corner of the window is the window background
+        assert_ne!(image.get_pixel(2, 2).0, [255, 0, 0, 255]);
+    }
+}
@@ -9,5 +9,7 @@ pub use easy_imgui_sys;
 // Handy re-exports of core dependencies
 pub use easy_imgui_opengl::{self, glow};
 
+mod headless;
 mod renderer;
+pub use headless::*;
 pub use renderer::*;
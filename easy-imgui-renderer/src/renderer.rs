@@ -1,3 +1,4 @@
+use std::cell::RefCell;
 use std::mem::size_of;
 
 use crate::glow::{self, HasContext};
@@ -14,7 +15,10 @@ pub struct Renderer {
     gl: glr::GlContext,
     bg_color: Option<imgui::Color>,
     matrix: Option<Matrix3<f32>>,
+    srgb: bool,
+    framebuffer_scale: Option<Vector2>,
     objs: GlObjects,
+    draw_calls: usize,
 }
 
 struct GlObjects {
@@ -28,6 +32,113 @@ struct GlObjects {
     a_color_location: u32,
     u_matrix_location: glow::UniformLocation,
     u_tex_location: glow::UniformLocation,
+    u_srgb_location: glow::UniformLocation,
+}
+
+/// The OpenGL state available to a callback added with [`GlCallbackExt::add_gl_callback`].
+pub struct RenderContext {
+    gl: glr::GlContext,
+    matrix: Matrix3<f32>,
+    clip_min: Vector2,
+    clip_max: Vector2,
+}
+
+impl RenderContext {
+    /// Gets the OpenGL context.
+    pub fn gl(&self) -> &glr::GlContext {
+        &self.gl
+    }
+    /// Gets the matrix that transforms UI coordinates to clip coordinates.
+    pub fn matrix(&self) -> &Matrix3<f32> {
+        &self.matrix
+    }
+    /// Gets the clip rectangle of the draw command, in UI coordinates.
+    ///
+    /// The scissor test is already set up to this rectangle.
+    pub fn clip_rect(&self) -> (Vector2, Vector2) {
+        (self.clip_min, self.clip_max)
+    }
+}
+
+/// How a texture is sampled, see [`Renderer::load_texture_from_image`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct TextureOptions {
+    /// Filter used when the texture is drawn smaller than its size.
+    pub min_filter: TextureFilter,
+    /// Filter used when the texture is drawn bigger than its size.
+    pub mag_filter: TextureFilter,
+    /// Wrapping of the horizontal texture coordinates.
+    pub wrap_s: TextureWrap,
+    /// Wrapping of the vertical texture coordinates.
+    pub wrap_t: TextureWrap,
+}
+
+/// The filter of a texture, see [`TextureOptions`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum TextureFilter {
+    /// Interpolates the nearby pixels, for smooth scaling.
+    #[default]
+    Linear,
+    /// Takes the nearest pixel, for pixel art.
+    Nearest,
+}
+
+/// The wrapping of a texture, see [`TextureOptions`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum TextureWrap {
+    /// Coordinates outside the texture take the color of the edge.
+    #[default]
+    ClampToEdge,
+    /// The texture is repeated.
+    Repeat,
+    /// The texture is repeated, mirrored every other time.
+    MirroredRepeat,
+}
+
+impl TextureFilter {
+    fn gl(self) -> i32 {
+        (match self {
+            TextureFilter::Linear => glow::LINEAR,
+            TextureFilter::Nearest => glow::NEAREST,
+        }) as i32
+    }
+}
+
+impl TextureWrap {
+    fn gl(self) -> i32 {
+        (match self {
+            TextureWrap::ClampToEdge => glow::CLAMP_TO_EDGE,
+            TextureWrap::Repeat => glow::REPEAT,
+            TextureWrap::MirroredRepeat => glow::MIRRORED_REPEAT,
+        }) as i32
+    }
+}
+
+thread_local! {
+    // Only set while a user callback is being run.
+    static RENDER_CONTEXT: RefCell<Option<RenderContext>> = const { RefCell::new(None) };
+}
+
+/// Extension trait to add draw callbacks that issue their own OpenGL calls.
+pub trait GlCallbackExt<A> {
+    /// Adds a callback to the draw list that can draw using OpenGL.
+    ///
+    /// It is called while rendering, with the current state of the renderer. Feel free to bind
+    /// your own programs, buffers and textures, or to change the viewport: the renderer will
+    /// restore its state afterwards.
+    fn add_gl_callback(&self, cb: impl FnOnce(&mut A, &RenderContext) + 'static);
+}
+
+impl<A> GlCallbackExt<A> for imgui::WindowDrawList<'_, A> {
+    fn add_gl_callback(&self, cb: impl FnOnce(&mut A, &RenderContext) + 'static) {
+        self.add_callback(move |a| {
+            RENDER_CONTEXT.with(|ctx| {
+                if let Some(ctx) = &*ctx.borrow() {
+                    cb(a, ctx);
+                }
+            });
+        });
+    }
 }
 
 impl Renderer {
@@ -44,6 +155,7 @@ impl Renderer {
         let a_color_location;
         let u_matrix_location;
         let u_tex_location;
+        let u_srgb_location;
 
         let imgui = unsafe { imgui::Context::new() };
 
@@ -84,6 +196,9 @@ impl Renderer {
             let u_tex = program.uniform_by_name("tex").unwrap();
             u_tex_location = u_tex.location();
 
+            let u_srgb = program.uniform_by_name("srgb").unwrap();
+            u_srgb_location = u_srgb.location();
+
             vbuf = glr::Buffer::generate(&gl)?;
             ibuf = glr::Buffer::generate(&gl)?;
         }
@@ -92,6 +207,8 @@ impl Renderer {
             gl,
             bg_color: Some(Color::new(0.45, 0.55, 0.60, 1.0)),
             matrix: None,
+            srgb: false,
+            framebuffer_scale: None,
             objs: GlObjects {
                 atlas,
                 program,
@@ -103,7 +220,9 @@ impl Renderer {
                 a_color_location,
                 u_matrix_location,
                 u_tex_location,
+                u_srgb_location,
             },
+            draw_calls: 0,
         })
     }
     /// Gets a reference to the OpenGL context.
@@ -124,6 +243,33 @@ impl Renderer {
     pub fn set_matrix(&mut self, matrix: Option<Matrix3<f32>>) {
         self.matrix = matrix;
     }
+    /// Enables the gamma-correct rendering mode, for sRGB framebuffers.
+    ///
+    /// Dear ImGui colors are defined in sRGB space. If your framebuffer is sRGB capable and you
+    /// set this to `true`, the vertex colors will be converted to linear space in the shader and
+    /// `GL_FRAMEBUFFER_SRGB` will be enabled while rendering, so that the final colors are the
+    /// expected ones. User textures should then use an sRGB internal format, too.
+    ///
+    /// By default it is `false`, and colors are written to the framebuffer unchanged.
+    pub fn set_srgb(&mut self, srgb: bool) {
+        self.srgb = srgb;
+    }
+    /// Gets whether the gamma-correct rendering mode is enabled.
+    pub fn srgb(&self) -> bool {
+        self.srgb
+    }
+    /// Sets the scale from UI units to framebuffer pixels, overriding the one from [`Renderer::set_size`].
+    ///
+    /// This is useful when the framebuffer size is not exactly the UI size times the scale factor,
+    /// such as with fractional DPI scaling, or if the X and Y scales differ.
+    /// Set to `None` to use the scale factor from `set_size`, the default.
+    pub fn set_framebuffer_scale(&mut self, scale: Option<Vector2>) {
+        self.framebuffer_scale = scale;
+    }
+    /// Gets the scale from UI units to framebuffer pixels, if overridden.
+    pub fn framebuffer_scale(&self) -> Option<Vector2> {
+        self.framebuffer_scale
+    }
     /// Gets the background color.
     pub fn background_color(&self) -> Option<Color> {
         self.bg_color
@@ -142,6 +288,12 @@ impl Renderer {
     pub fn size(&mut self) -> Vector2 {
         unsafe { self.imgui.set_current().size() }
     }
+    /// Gets the number of draw calls issued to render the last frame.
+    ///
+    /// User callbacks are not counted.
+    pub fn last_frame_draw_calls(&self) -> usize {
+        self.draw_calls
+    }
     /// Builds and renders a UI frame, using the `app` [`easy_imgui::UiBuilder`].
     pub fn do_frame<A: imgui::UiBuilder>(&mut self, app: &mut A) {
         unsafe {
@@ -151,6 +303,10 @@ impl Renderer {
                 Self::update_atlas(&self.gl, &self.objs.atlas);
             }
 
+            let fb_scale = self.framebuffer_scale.unwrap_or_else(|| {
+                let io = &*ImGui_GetIO();
+                Vector2::new(io.DisplayFramebufferScale.x, io.DisplayFramebufferScale.y)
+            });
             imgui.do_frame(
                 app,
                 || {
@@ -159,8 +315,8 @@ impl Renderer {
                         self.gl.viewport(
                             0,
                             0,
-                            (io.DisplaySize.x * io.DisplayFramebufferScale.x) as i32,
-                            (io.DisplaySize.y * io.DisplayFramebufferScale.y) as i32,
+                            (io.DisplaySize.x * fb_scale.x).round() as i32,
+                            (io.DisplaySize.y * fb_scale.y).round() as i32,
                         );
                     }
                     if let Some(bg) = self.bg_color {
@@ -169,7 +325,14 @@ impl Renderer {
                     }
                 },
                 |draw_data| {
-                    Self::render(&self.gl, &self.objs, draw_data, self.matrix.as_ref());
+                    self.draw_calls = Self::render(
+                        &self.gl,
+                        &self.objs,
+                        draw_data,
+                        self.matrix.as_ref(),
+                        self.srgb,
+                        fb_scale,
+                    );
                 },
             );
         }
@@ -234,12 +397,43 @@ impl Renderer {
         // We keep this, no need for imgui to hold a copy
         ImFontAtlas_ClearTexData((*io).Fonts);
     }
-    unsafe fn render(
+    unsafe fn setup_render_state(
         gl: &glow::Context,
         objs: &GlObjects,
+        scissor: bool,
+        framebuffer_srgb: bool,
+    ) {
+        if scissor {
+            gl.enable(glow::SCISSOR_TEST);
+        } else {
+            gl.disable(glow::SCISSOR_TEST);
+        }
+        gl.bind_vertex_array(Some(objs.vao.id()));
+        gl.use_program(Some(objs.program.id()));
+        gl.bind_buffer(glow::ARRAY_BUFFER, Some(objs.vbuf.id()));
+        gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(objs.ibuf.id()));
+        gl.enable(glow::BLEND);
+        gl.blend_func_separate(
+            glow::SRC_ALPHA,
+            glow::ONE_MINUS_SRC_ALPHA,
+            glow::ONE,
+            glow::ONE_MINUS_SRC_ALPHA,
+        );
+        gl.disable(glow::CULL_FACE);
+        gl.disable(glow::DEPTH_TEST);
+        if framebuffer_srgb {
+            gl.enable(glow::FRAMEBUFFER_SRGB);
+        }
+        gl.active_texture(glow::TEXTURE0);
+    }
+    unsafe fn render(
+        gl: &glr::GlContext,
+        objs: &GlObjects,
         draw_data: &ImDrawData,
         matrix: Option<&Matrix3<f32>>,
-    ) {
+        srgb: bool,
+        fb_scale: Vector2,
+    ) -> usize {
         enum ScissorViewportMatrix {
             Default,
             Custom(Matrix3<f32>),
@@ -255,7 +449,6 @@ impl Renderer {
                 } = draw_data.DisplaySize;
                 let right = left + width;
                 let bottom = top + height;
-                gl.enable(glow::SCISSOR_TEST);
                 default_matrix = Matrix3::new(
                     2.0 / width,
                     0.0,
@@ -296,31 +489,20 @@ impl Renderer {
                         viewport_y + viewport_h2,
                         1.0,
                     );
-                    gl.enable(glow::SCISSOR_TEST);
                     (matrix, ScissorViewportMatrix::Custom(vm * matrix))
                 } else {
-                    gl.disable(glow::SCISSOR_TEST);
                     (matrix, ScissorViewportMatrix::None)
                 }
             }
         };
 
-        gl.bind_vertex_array(Some(objs.vao.id()));
-        gl.use_program(Some(objs.program.id()));
-        gl.bind_buffer(glow::ARRAY_BUFFER, Some(objs.vbuf.id()));
-        gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(objs.ibuf.id()));
-        gl.enable(glow::BLEND);
-        gl.blend_func_separate(
-            glow::SRC_ALPHA,
-            glow::ONE_MINUS_SRC_ALPHA,
-            glow::ONE,
-            glow::ONE_MINUS_SRC_ALPHA,
-        );
-        gl.disable(glow::CULL_FACE);
-        gl.disable(glow::DEPTH_TEST);
+        let scissor = !matches!(viewport_matrix, ScissorViewportMatrix::None);
+        // WebGL has no GL_FRAMEBUFFER_SRGB, the canvas does the conversion, if any
+        let framebuffer_srgb = srgb && !cfg!(target_arch = "wasm32");
+        Self::setup_render_state(gl, objs, scissor, framebuffer_srgb);
 
-        gl.active_texture(glow::TEXTURE0);
         gl.uniform_1_i32(Some(&objs.u_tex_location), 0);
+        gl.uniform_1_i32(Some(&objs.u_srgb_location), srgb as i32);
 
         gl.uniform_matrix_3_f32_slice(
             Some(&objs.u_matrix_location),
@@ -328,6 +510,8 @@ impl Renderer {
             AsRef::<[f32; 9]>::as_ref(matrix),
         );
 
+        let mut draw_calls = 0;
+
         for cmd_list in &draw_data.CmdLists {
             let cmd_list = &**cmd_list;
 
@@ -370,17 +554,15 @@ impl Renderer {
             for cmd in &cmd_list.CmdBuffer {
                 match viewport_matrix {
                     ScissorViewportMatrix::Default => {
-                        let clip_x = cmd.ClipRect.x - draw_data.DisplayPos.x;
-                        let clip_y = cmd.ClipRect.y - draw_data.DisplayPos.y;
-                        let clip_w = cmd.ClipRect.z - cmd.ClipRect.x;
-                        let clip_h = cmd.ClipRect.w - cmd.ClipRect.y;
-                        let scale = draw_data.FramebufferScale.x;
-                        gl.scissor(
-                            (clip_x * scale) as i32,
-                            ((draw_data.DisplaySize.y - (clip_y + clip_h)) * scale) as i32,
-                            (clip_w * scale) as i32,
-                            (clip_h * scale) as i32,
-                        );
+                        // Round each edge separately, so that adjacent clip rectangles match
+                        // exactly even with fractional scales.
+                        let pos = draw_data.DisplayPos;
+                        let min_x = ((cmd.ClipRect.x - pos.x) * fb_scale.x).round() as i32;
+                        let min_y = ((cmd.ClipRect.y - pos.y) * fb_scale.y).round() as i32;
+                        let max_x = ((cmd.ClipRect.z - pos.x) * fb_scale.x).round() as i32;
+                        let max_y = ((cmd.ClipRect.w - pos.y) * fb_scale.y).round() as i32;
+                        let fb_height = (draw_data.DisplaySize.y * fb_scale.y).round() as i32;
+                        gl.scissor(min_x, fb_height - max_y, max_x - min_x, max_y - min_y);
                     }
                     ScissorViewportMatrix::Custom(vm) => {
                         let pos = Vector2::new(draw_data.DisplayPos.x, draw_data.DisplayPos.y);
@@ -400,9 +582,24 @@ impl Renderer {
 
                 match cmd.UserCallback {
                     Some(cb) => {
+                        let ctx = RenderContext {
+                            gl: gl.clone(),
+                            matrix: *matrix,
+                            clip_min: Vector2::new(cmd.ClipRect.x, cmd.ClipRect.y),
+                            clip_max: Vector2::new(cmd.ClipRect.z, cmd.ClipRect.w),
+                        };
+                        // The viewport is not set by `setup_render_state`, so save it for later
+                        let mut viewport = [0; 4];
+                        gl.get_parameter_i32_slice(glow::VIEWPORT, &mut viewport);
+                        RENDER_CONTEXT.with(|c| *c.borrow_mut() = Some(ctx));
                         cb(cmd_list, cmd);
+                        RENDER_CONTEXT.with(|c| *c.borrow_mut() = None);
+                        // The callback may have changed anything
+                        gl.viewport(viewport[0], viewport[1], viewport[2], viewport[3]);
+                        Self::setup_render_state(gl, objs, scissor, framebuffer_srgb);
                     }
                     None => {
+                        draw_calls += 1;
                         gl.bind_texture(
                             glow::TEXTURE_2D,
                             Self::unmap_tex(TextureId::from_id(cmd.TextureId)),
@@ -439,6 +636,10 @@ impl Renderer {
         gl.use_program(None);
         gl.bind_vertex_array(None);
         gl.disable(glow::SCISSOR_TEST);
+        if framebuffer_srgb {
+            gl.disable(glow::FRAMEBUFFER_SRGB);
+        }
+        draw_calls
     }
     /// Maps an OpenGL texture to an ImGui texture.
     pub fn map_tex(ntex: glow::Texture) -> TextureId {
@@ -469,6 +670,86 @@ impl Renderer {
             )?))
         }
     }
+    /// Creates a new texture with the contents of `image`.
+    ///
+    /// Use [`Renderer::map_tex`] to get the `TextureId` to be used in the UI. The texture is
+    /// destroyed when the returned value is dropped.
+    pub fn load_texture_from_image(
+        &self,
+        image: &imgui::image::RgbaImage,
+        options: TextureOptions,
+    ) -> Result<glr::Texture> {
+        let gl = &self.gl;
+        let tex = glr::Texture::generate(gl)?;
+        unsafe {
+            gl.bind_texture(glow::TEXTURE_2D, Some(tex.id()));
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_S, options.wrap_s.gl());
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_WRAP_T, options.wrap_t.gl());
+            gl.tex_parameter_i32(
+                glow::TEXTURE_2D,
+                glow::TEXTURE_MIN_FILTER,
+                options.min_filter.gl(),
+            );
+            gl.tex_parameter_i32(
+                glow::TEXTURE_2D,
+                glow::TEXTURE_MAG_FILTER,
+                options.mag_filter.gl(),
+            );
+            gl.tex_parameter_i32(glow::TEXTURE_2D, glow::TEXTURE_MAX_LEVEL, 0);
+            gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1);
+            gl.tex_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                glow::RGBA as i32,
+                image.width() as i32,
+                image.height() as i32,
+                0,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelUnpackData::Slice(Some(image.as_raw())),
+            );
+            gl.bind_texture(glow::TEXTURE_2D, None);
+        }
+        glr::check_gl(gl)?;
+        Ok(tex)
+    }
+    /// Replaces all the pixels of an existing texture.
+    ///
+    /// The texture must have the same size as `image`. It is not reallocated, so it is
+    /// suitable for streaming content, such as video, that changes every frame.
+    pub fn update_texture(&self, tex: TextureId, image: &imgui::image::RgbaImage) -> Result<()> {
+        self.update_texture_region(tex, [0, 0], image)
+    }
+    /// Replaces a rectangle of the pixels of an existing texture.
+    ///
+    /// The rectangle starts at `offset` and has the size of `image`, that must fit inside the texture.
+    pub fn update_texture_region(
+        &self,
+        tex: TextureId,
+        offset: [u32; 2],
+        image: &imgui::image::RgbaImage,
+    ) -> Result<()> {
+        let ntex = Self::unmap_tex(tex).ok_or_else(|| anyhow!("invalid texture"))?;
+        let gl = &self.gl;
+        unsafe {
+            gl.bind_texture(glow::TEXTURE_2D, Some(ntex));
+            gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1);
+            gl.tex_sub_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                offset[0] as i32,
+                offset[1] as i32,
+                image.width() as i32,
+                image.height() as i32,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelUnpackData::Slice(Some(image.as_raw())),
+            );
+            gl.bind_texture(glow::TEXTURE_2D, None);
+        }
+        glr::check_gl(gl)?;
+        Ok(())
+    }
 }
 
 #[cfg(target_arch = "wasm32")]
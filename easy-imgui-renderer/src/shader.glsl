@@ -7,12 +7,17 @@ out vec2 v_uv;
 out vec4 v_color;
 
 uniform mat3 matrix;
+uniform bool srgb;
+
+vec3 srgb_to_linear(vec3 c) {
+    return mix(c / 12.92, pow((c + 0.055) / 1.055, vec3(2.4)), step(0.04045, c));
+}
 
 void main(void) {
     vec3 tpos = matrix * vec3(pos, 1.0);
     gl_Position = vec4(tpos.xy, 0.0, tpos.z);
     v_uv = uv;
-    v_color = color;
+    v_color = srgb ? vec4(srgb_to_linear(color.rgb), color.a) : color;
 }
 
 ###
@@ -23,3 +23,4 @@ ImVec2_rr ImGui_CalcTextSize(const char* text, const char* text_end, bool hide_t
 ImVec2_rr ImGui_GetMousePos() { return _rr(ImGui::GetMousePos()); }
 ImVec2_rr ImGui_GetMousePosOnOpeningCurrentPopup() { return _rr(ImGui::GetMousePosOnOpeningCurrentPopup()); }
 ImVec2_rr ImGui_GetMouseDragDelta(ImGuiMouseButton button, float lock_threshold) { return _rr(ImGui::GetMouseDragDelta(button, lock_threshold)); }
+ImVec2_rr ImGui_CalcItemSize(ImVec2 size, float default_w, float default_h) { return _rr(ImGui::CalcItemSize(size, default_w, default_h)); }
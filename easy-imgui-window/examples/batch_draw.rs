@@ -0,0 +1,106 @@
+use easy_imgui_window::{easy_imgui as imgui, winit, AppHandler, Application, Args, EventResult};
+use imgui::{lbl, vec2, Color, Vector2};
+use std::time::{Duration, Instant};
+use winit::{event::WindowEvent, event_loop::EventLoop};
+
+// Compares the time to add many lines to a draw list one by one, with `add_line`, and in a
+// single batch, with `add_lines`. Run it in release mode for meaningful timings.
+
+const NUM_LINES: usize = 10_000;
+const NUM_SAMPLES: usize = 60;
+
+fn main() {
+    let event_loop = EventLoop::new().unwrap();
+
+    let mut main = AppHandler::<App>::default();
+    main.attributes().title = String::from("Batch drawing");
+
+    event_loop.run_app(&mut main).unwrap();
+}
+
+struct App {
+    batched: bool,
+    // The lines, relative to the origin of the drawing area, in the range 0..1
+    lines: Vec<(Vector2, Vector2)>,
+    // The time spent adding the lines, in the last frames
+    samples: Vec<Duration>,
+}
+
+impl Application for App {
+    type UserEvent = ();
+    type Data = ();
+    fn new(_: Args<()>) -> App {
+        // A simple LCG, the lines just have to look random
+        let mut seed = 0x2545_f491_u32;
+        let mut rand = move || {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (seed >> 8) as f32 / (1 << 24) as f32
+        };
+        let lines = (0..NUM_LINES)
+            .map(|_| (vec2(rand(), rand()), vec2(rand(), rand())))
+            .collect();
+        App {
+            batched: false,
+            lines,
+            samples: Vec::with_capacity(NUM_SAMPLES),
+        }
+    }
+    fn window_event(&mut self, args: Args<()>, _event: WindowEvent, res: EventResult) {
+        if res.window_closed {
+            args.event_loop.exit();
+        }
+        // Keep redrawing, to keep measuring
+        args.window.ping_user_input();
+    }
+}
+
+impl imgui::UiBuilder for App {
+    fn do_ui(&mut self, ui: &imgui::Ui<Self>) {
+        ui.set_next_window_size(vec2(600.0, 500.0), imgui::Cond::FirstUseEver);
+        ui.window_config(lbl("Batch drawing")).with(|| {
+            if ui.checkbox(lbl("Batched"), &mut self.batched) {
+                self.samples.clear();
+            }
+            let average = if self.samples.is_empty() {
+                Duration::ZERO
+            } else {
+                self.samples.iter().sum::<Duration>() / self.samples.len() as u32
+            };
+            let func = if self.batched {
+                "add_lines"
+            } else {
+                "add_line"
+            };
+            ui.text(&format!(
+                "{NUM_LINES} lines with {func}: {:.3} ms per frame",
+                average.as_secs_f64() * 1000.0,
+            ));
+
+            let pos = ui.get_cursor_screen_pos();
+            let size = ui.get_content_region_avail();
+            let dl = ui.window_draw_list();
+            let color = Color::new(0.4, 0.8, 1.0, 0.25);
+            let to_screen = |p: Vector2| pos + vec2(p.x * size.x, p.y * size.y);
+
+            let start = Instant::now();
+            if self.batched {
+                let lines: Vec<_> = self
+                    .lines
+                    .iter()
+                    .map(|&(a, b)| (to_screen(a), to_screen(b)))
+                    .collect();
+                dl.add_lines(&lines, color, 1.0);
+            } else {
+                for &(a, b) in &self.lines {
+                    dl.add_line(to_screen(a), to_screen(b), color, 1.0);
+                }
+            }
+            let elapsed = start.elapsed();
+
+            if self.samples.len() == NUM_SAMPLES {
+                self.samples.remove(0);
+            }
+            self.samples.push(elapsed);
+        });
+    }
+}
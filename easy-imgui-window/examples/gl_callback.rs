@@ -0,0 +1,135 @@
+use easy_imgui_window::{
+    easy_imgui as imgui,
+    easy_imgui_renderer::{
+        easy_imgui_opengl::{self as glr, GlContext},
+        glow, GlCallbackExt, RenderContext,
+    },
+    winit, AppHandler, Application, Args, EventResult,
+};
+use imgui::{lbl, vec2};
+use winit::{event::WindowEvent, event_loop::EventLoop};
+
+const VSH: &str = r"#version 140
+uniform mat3 m;
+in vec2 pos;
+in vec3 color;
+out vec3 v_color;
+void main(void) {
+    vec3 tpos = m * vec3(pos, 1.0);
+    gl_Position = vec4(tpos.xy, 0.0, tpos.z);
+    v_color = color;
+}
+";
+const FSH: &str = r"#version 140
+in vec3 v_color;
+out vec4 out_frag_color;
+void main(void) {
+    out_frag_color = vec4(v_color, 1.0);
+}
+";
+
+type Matrix3 = imgui::cgmath::Matrix3<f32>;
+type Vector2 = imgui::cgmath::Vector2<f32>;
+type Vector3 = imgui::cgmath::Vector3<f32>;
+
+glr::uniform! {
+    struct Uniform {
+        m: Matrix3,
+    }
+}
+
+glr::attrib! {
+    #[derive(Copy, Clone)]
+    struct Vertex {
+        pos: Vector2,
+        color: Vector3,
+    }
+}
+
+fn main() {
+    let event_loop = EventLoop::new().unwrap();
+
+    let mut main = AppHandler::<App>::default();
+    main.attributes().title = String::from("GL callback");
+
+    event_loop.run_app(&mut main).unwrap();
+}
+
+struct App {
+    vao: glr::VertexArray,
+    prg: glr::Program,
+    ds: glr::DynamicVertexArray<Vertex>,
+    angle: f32,
+}
+
+impl Application for App {
+    type UserEvent = ();
+    type Data = ();
+    fn new(args: Args<()>) -> App {
+        let gl: &GlContext = args.window.renderer().gl_context();
+        App {
+            vao: glr::VertexArray::generate(gl).unwrap(),
+            prg: glr::Program::from_source(gl, VSH, FSH, None).unwrap(),
+            ds: glr::DynamicVertexArray::new(gl).unwrap(),
+            angle: 0.0,
+        }
+    }
+    fn window_event(&mut self, args: Args<()>, _event: WindowEvent, res: EventResult) {
+        if res.window_closed {
+            args.event_loop.exit();
+        }
+    }
+}
+
+impl App {
+    // Draws a triangle centered in the given rectangle, big enough to be clipped by it.
+    fn draw_triangle(&mut self, ctx: &RenderContext, center: Vector2, radius: f32) {
+        use glow::HasContext;
+
+        let colors = [
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        ];
+        let vertices: Vec<Vertex> = colors
+            .iter()
+            .enumerate()
+            .map(|(i, &color)| {
+                let a = self.angle + i as f32 * std::f32::consts::TAU / 3.0;
+                Vertex {
+                    pos: center + Vector2::new(a.cos(), a.sin()) * radius,
+                    color,
+                }
+            })
+            .collect();
+        self.ds.set(vertices);
+        let u = Uniform { m: *ctx.matrix() };
+        unsafe {
+            ctx.gl().bind_vertex_array(Some(self.vao.id()));
+        }
+        self.prg.draw(&u, &self.ds, glow::TRIANGLES);
+    }
+}
+
+impl imgui::UiBuilder for App {
+    fn do_ui(&mut self, ui: &imgui::Ui<Self>) {
+        self.angle += ui.io().DeltaTime;
+
+        ui.set_next_window_size(vec2(400.0, 300.0), imgui::Cond::FirstUseEver);
+        ui.window_config(lbl("3D view")).with(|| {
+            ui.text("The triangle is drawn with OpenGL, clipped to the child window:");
+            ui.child_config(lbl("view"))
+                .child_flags(imgui::ChildFlags::Borders)
+                .with(|| {
+                    let pos = ui.get_cursor_screen_pos();
+                    let size = ui.get_content_region_avail();
+                    let center = pos + size / 2.0;
+                    let radius = size.x.max(size.y) / 2.0;
+                    ui.window_draw_list()
+                        .add_gl_callback(move |app: &mut App, ctx| {
+                            app.draw_triangle(ctx, center, radius)
+                        });
+                });
+        });
+    }
+}
@@ -11,7 +11,7 @@ use glutin::{
 use std::num::NonZeroU32;
 use std::time::{Duration, Instant};
 use winit::{
-    dpi::{LogicalSize, PhysicalSize},
+    dpi::{LogicalSize, PhysicalPosition, PhysicalSize},
     event::Ime::Commit,
     keyboard::PhysicalKey,
     window::{CursorIcon, Window},
@@ -19,13 +19,34 @@ use winit::{
 
 // Only used with the main-window feature
 #[allow(unused_imports)]
-use winit::dpi::{LogicalPosition, PhysicalPosition, Pixel};
+use winit::dpi::{LogicalPosition, Pixel};
 
 /// This struct maintains basic window info to be kept across events.
 #[derive(Debug, Clone)]
 pub struct MainWindowStatus {
     last_frame: Instant,
     current_cursor: Option<CursorIcon>,
+    ime_enabled: bool,
+    // Last position and line height sent to the IME, in ImGui coordinates.
+    ime_area: Option<(Vector2, f32)>,
+}
+
+impl MainWindowStatus {
+    /// Enables or disables the IME input, enabled by default.
+    ///
+    /// When enabled, the IME candidate window is moved to the active text input.
+    ///
+    /// This lives here and not in `easy_imgui::Context` because only the window can talk to the
+    /// IME: Dear ImGui just computes where the text input is, and the core crate has no window.
+    pub fn set_ime_enabled(&mut self, main_window: &impl MainWindowRef, enabled: bool) {
+        self.ime_enabled = enabled;
+        self.ime_area = None;
+        main_window.window().set_ime_allowed(enabled);
+    }
+    /// Gets whether the IME input is enabled.
+    pub fn ime_enabled(&self) -> bool {
+        self.ime_enabled
+    }
 }
 
 impl Default for MainWindowStatus {
@@ -34,6 +55,8 @@ impl Default for MainWindowStatus {
         MainWindowStatus {
             last_frame: now,
             current_cursor: Some(CursorIcon::Default),
+            ime_enabled: true,
+            ime_area: None,
         }
     }
 }
@@ -281,6 +304,31 @@ pub fn about_to_wait(main_window: &mut impl MainWindowRef, renderer: &mut Render
     main_window.about_to_wait(mouse);
 }
 
+// Moves the IME candidate window to the text input position requested by ImGui.
+//
+// Instead of installing a `Platform_SetImeDataFn` callback, that would be called from inside the
+// ImGui frame without a way to reach the window, this reads the same data from the context after
+// the frame, as it is stored in `PlatformImeData`.
+fn update_ime(main_window: &impl MainWindowRef, status: &mut MainWindowStatus) {
+    if !status.ime_enabled {
+        return;
+    }
+    let data = unsafe { &(*ImGui_GetCurrentContext()).PlatformImeData };
+    if !data.WantVisible {
+        status.ime_area = None;
+        return;
+    }
+    let area = (imgui::im_to_v2(data.InputPos), data.InputLineHeight);
+    if status.ime_area == Some(area) {
+        return;
+    }
+    status.ime_area = Some(area);
+    let scale = main_window.scale_factor();
+    let pos = PhysicalPosition::new(area.0.x * scale, area.0.y * scale);
+    let size = PhysicalSize::new(scale, area.1 * scale);
+    main_window.window().set_ime_cursor_area(pos, size);
+}
+
 pub fn window_event(
     main_window: &mut impl MainWindowRef,
     renderer: &mut Renderer,
@@ -316,6 +364,7 @@ pub fn window_event(
                 main_window.pre_render();
                 renderer.do_frame(app);
                 main_window.post_render();
+                update_ime(main_window, status);
             }
         },
         Resized(size) => {
@@ -747,6 +796,12 @@ mod main_window {
         pub fn ping_user_input(&mut self) {
             self.main_window.idler.ping_user_input();
         }
+        /// Enables or disables the IME input, enabled by default.
+        ///
+        /// When enabled, the IME candidate window follows the active text input.
+        pub fn set_ime_enabled(&mut self, enabled: bool) {
+            self.status.set_ime_enabled(&self.main_window, enabled);
+        }
         /// Gets a reference to the inner renderer.
         pub fn renderer(&mut self) -> &mut Renderer {
             &mut self.renderer
@@ -314,6 +314,13 @@ imgui_scoped_enum! {
     }
 }
 
+imgui_scoped_enum! {
+    pub Axis: ImGuiAxis {
+        X,
+        Y,
+    }
+}
+
 imgui_flags! {
     pub ComboFlags: ImGuiComboFlags_ {
         None,
@@ -0,0 +1,168 @@
+//! A headless context to test the UI code, without a window or a renderer.
+//!
+//! The input events are sent between frames, so every test runs the frames that Dear ImGui needs
+//! to see the mouse move, the button press and the release.
+
+use super::*;
+
+/// A `UiBuilder` that calls a function to build the frame.
+pub struct TestApp<'a> {
+    f: Box<dyn FnMut(&Ui<TestApp<'a>>) + 'a>,
+}
+
+impl UiBuilder for TestApp<'_> {
+    fn build_custom_atlas(&mut self, atlas: &mut FontAtlasMut<'_, Self>) {
+        // The first font is the default one, the other is there to test pushing fonts
+        atlas.add_font(FontInfo::default_font(13.0));
+        atlas.add_font(FontInfo::default_font(20.0));
+    }
+    fn do_ui(&mut self, ui: &Ui<Self>) {
+        (self.f)(ui);
+    }
+}
+
+/// An ImGui context that runs frames on demand.
+pub struct Harness {
+    ctx: Context,
+}
+
+impl Harness {
+    pub fn new() -> Harness {
+        unsafe {
+            let mut ctx = Context::new();
+            ctx.set_current().set_size(Harness::size(), 1.0);
+            Harness { ctx }
+        }
+    }
+    /// The size of the display, the window of [`Harness::frame`] covers all of it.
+    pub fn size() -> Vector2 {
+        vec2(800.0, 600.0)
+    }
+    /// Gets the context, to send input events that will be used in the next frame.
+    pub fn input(&mut self) -> CurrentContext<'_> {
+        unsafe { self.ctx.set_current() }
+    }
+    /// Runs a frame, calling `f` to build the UI, outside of any window. Then calls `check` with
+    /// the rendered data.
+    pub fn raw_frame_with_draw_data(
+        &mut self,
+        f: impl FnMut(&Ui<TestApp<'_>>),
+        check: impl FnOnce(&ImDrawData),
+    ) {
+        let mut app = TestApp { f: Box::new(f) };
+        unsafe {
+            let mut ctx = self.ctx.set_current();
+            ctx.update_atlas(&mut app);
+            ctx.do_frame(&mut app, || {}, check);
+        }
+    }
+    /// Runs a frame, calling `f` inside a window that covers the whole display.
+    pub fn frame(&mut self, f: impl FnMut(&Ui<TestApp<'_>>)) {
+        self.frame_with_draw_data(f, |_| {});
+    }
+    /// Like `frame`, then calls `check` with the rendered data.
+    pub fn frame_with_draw_data(
+        &mut self,
+        mut f: impl FnMut(&Ui<TestApp<'_>>),
+        check: impl FnOnce(&ImDrawData),
+    ) {
+        self.raw_frame_with_draw_data(
+            |ui| {
+                ui.set_next_window_pos(vec2(0.0, 0.0), Cond::Always, vec2(0.0, 0.0));
+                ui.set_next_window_size(Harness::size(), Cond::Always);
+                ui.window_config(lbl(c"test"))
+                    .flags(WindowFlags::NoDecoration | WindowFlags::NoSavedSettings)
+                    .with(|| f(ui));
+            },
+            check,
+        );
+    }
+    /// Runs `n` frames, with `frame`.
+    pub fn frames(&mut self, n: usize, mut f: impl FnMut(&Ui<TestApp<'_>>)) {
+        for _ in 0..n {
+            self.frame(&mut f);
+        }
+    }
+    pub fn mouse_move(&mut self, pos: Vector2) {
+        self.input().add_mouse_pos_event(pos);
+    }
+    pub fn mouse_button(&mut self, down: bool) {
+        self.input().add_mouse_button_event(MouseButton::Left, down);
+    }
+    /// Moves the mouse to `pos` and runs a frame.
+    ///
+    /// The frame is one second long, so that a following click is never a double click.
+    pub fn hover(&mut self, pos: Vector2, f: impl FnMut(&Ui<TestApp<'_>>)) {
+        self.mouse_move(pos);
+        self.input().io_mut().DeltaTime = 1.0;
+        self.frame(f);
+        self.input().io_mut().DeltaTime = 1.0 / 60.0;
+    }
+    /// Clicks with the left button at `pos`, running a frame for each step.
+    pub fn click(&mut self, pos: Vector2, mut f: impl FnMut(&Ui<TestApp<'_>>)) {
+        self.hold(pos, 0, &mut f);
+    }
+    /// Like `click`, but holding the button down for `frames` more frames.
+    pub fn hold(&mut self, pos: Vector2, frames: usize, mut f: impl FnMut(&Ui<TestApp<'_>>)) {
+        self.hover(pos, &mut f);
+        self.mouse_button(true);
+        self.frames(frames + 1, &mut f);
+        self.mouse_button(false);
+        self.frame(&mut f);
+    }
+    /// Drags with the left button from `from` to `to`, in `steps` mouse movements.
+    pub fn drag(
+        &mut self,
+        from: Vector2,
+        to: Vector2,
+        steps: usize,
+        mut f: impl FnMut(&Ui<TestApp<'_>>),
+    ) {
+        self.hover(from, &mut f);
+        self.mouse_button(true);
+        self.frame(&mut f);
+        for i in 1..=steps {
+            self.mouse_move(from + (to - from) * (i as f32 / steps as f32));
+            self.frame(&mut f);
+        }
+        self.mouse_button(false);
+        self.frame(&mut f);
+    }
+    /// Types `text` and runs a frame.
+    pub fn type_text(&mut self, text: &str, f: impl FnMut(&Ui<TestApp<'_>>)) {
+        let mut ctx = self.input();
+        for c in text.chars() {
+            ctx.add_input_character(c);
+        }
+        self.frame(f);
+    }
+    /// Presses and releases `key`, running a frame for each.
+    pub fn press_key(&mut self, key: Key, mut f: impl FnMut(&Ui<TestApp<'_>>)) {
+        self.input().add_key_event(key, true);
+        self.frame(&mut f);
+        self.input().add_key_event(key, false);
+        self.frame(&mut f);
+    }
+    /// Checks if the window with this name has been built in the last frame.
+    pub fn is_window_active(&mut self, name: &CStr) -> bool {
+        unsafe {
+            let _ctx = self.ctx.set_current();
+            let window = ImGui_FindWindowByName(name.as_ptr());
+            !window.is_null() && (*window).Active
+        }
+    }
+}
+
+/// Gets the center of the last item, to click on it.
+pub fn item_center<A>(ui: &Ui<A>) -> Vector2 {
+    (ui.get_item_rect_min() + ui.get_item_rect_max()) / 2.0
+}
+
+/// Finds if any vertex in the draw data has exactly this color.
+pub fn has_vertex_color(draw_data: &ImDrawData, color: Color) -> bool {
+    let col = color.as_u32();
+    draw_data
+        .CmdLists
+        .iter()
+        .any(|&list| unsafe { (*list).VtxBuffer.iter().any(|v| v.col == col) })
+}
@@ -107,6 +107,7 @@
  *    `stb_truetype` library.
  *  * `docking`: Uses the `docking` branch of Dear ImGui. Note that this is considered somewhat
  *    experimental.
+ *  * `derive`: Enables `#[derive(ComboItem)]`, see [`ComboItem`].
  *
  * # Usage
  * It is easier to use one of the higher level crates [`easy-imgui-window`] or [`easy-imgui-renderer`].
@@ -172,7 +173,7 @@ use std::cell::{Cell, RefCell};
 use std::ffi::{c_char, c_void, CStr, CString, OsString};
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
-use std::ops::Deref;
+use std::ops::{Deref, Range};
 use std::ptr::{null, null_mut};
 
 /// A type alias of the `cgmath::Vector2<f32>`.
@@ -182,14 +183,20 @@ use std::ptr::{null, null_mut};
 pub type Vector2 = cgmath::Vector2<f32>;
 
 mod enums;
+#[cfg(test)]
+mod harness;
 mod multisel;
 pub mod style;
+mod widgets;
 
+#[cfg(feature = "derive")]
+pub use easy_imgui_derive::ComboItem;
 pub use easy_imgui_sys::{self, ImGuiID, ImGuiSelectionUserData};
 pub use enums::*;
 pub use image;
 pub use mint;
 pub use multisel::*;
+pub use widgets::*;
 
 use image::GenericImage;
 
@@ -268,6 +275,29 @@ impl Color {
     pub fn as_u32(&self) -> u32 {
         unsafe { ImGui_ColorConvertFloat4ToU32(&(*self).into()) }
     }
+    /// Returns the same color with a different alpha.
+    pub const fn with_alpha(self, a: f32) -> Color {
+        Color { a, ..self }
+    }
+    /// Interpolates linearly between `self` and `other`, all four components.
+    ///
+    /// `t == 0.0` is `self` and `t == 1.0` is `other`. It is not clamped.
+    ///
+    /// ```
+    /// # use easy_imgui::Color;
+    /// let (a, b) = (Color::BLACK, Color::WHITE.with_alpha(0.0));
+    /// assert_eq!(a.lerp(b, 0.0), a);
+    /// assert_eq!(a.lerp(b, 0.5), Color::new(0.5, 0.5, 0.5, 0.5));
+    /// assert_eq!(a.lerp(b, 1.0), b);
+    /// ```
+    pub fn lerp(&self, other: Color, t: f32) -> Color {
+        Color::new(
+            self.r + (other.r - self.r) * t,
+            self.g + (other.g - self.g) * t,
+            self.b + (other.b - self.b) * t,
+            self.a + (other.a - self.a) * t,
+        )
+    }
 }
 impl AsRef<[f32; 4]> for Color {
     fn as_ref(&self) -> &[f32; 4] {
@@ -395,6 +425,45 @@ impl CurrentContext<'_> {
         let io = ImGui_GetIO();
         (*io).ConfigFlags &= !flags.bits();
     }
+    /// Queues a key press or release, as if it came from the platform backend.
+    ///
+    /// This and the following functions are useful to drive the UI from tests or
+    /// to write a custom input backend.
+    pub fn add_key_event(&mut self, key: Key, down: bool) {
+        unsafe {
+            ImGuiIO_AddKeyEvent(ImGui_GetIO(), key.bits(), down);
+        }
+    }
+    /// Queues a mouse move, in UI coordinates.
+    pub fn add_mouse_pos_event(&mut self, pos: Vector2) {
+        unsafe {
+            ImGuiIO_AddMousePosEvent(ImGui_GetIO(), pos.x, pos.y);
+        }
+    }
+    /// Queues a mouse button press or release.
+    pub fn add_mouse_button_event(&mut self, button: MouseButton, down: bool) {
+        unsafe {
+            ImGuiIO_AddMouseButtonEvent(ImGui_GetIO(), button.bits(), down);
+        }
+    }
+    /// Queues a mouse wheel movement, `x` is horizontal and `y` is vertical.
+    pub fn add_mouse_wheel_event(&mut self, wheel: Vector2) {
+        unsafe {
+            ImGuiIO_AddMouseWheelEvent(ImGui_GetIO(), wheel.x, wheel.y);
+        }
+    }
+    /// Queues a text character input.
+    pub fn add_input_character(&mut self, c: char) {
+        unsafe {
+            ImGuiIO_AddInputCharacter(ImGui_GetIO(), u32::from(c));
+        }
+    }
+    /// Queues a change of the focus of the application window.
+    pub fn add_focus_event(&mut self, focused: bool) {
+        unsafe {
+            ImGuiIO_AddFocusEvent(ImGui_GetIO(), focused);
+        }
+    }
     pub fn nav_enable_keyboard(&mut self) {
         unsafe {
             self.add_config_flags(ConfigFlags::NavEnableKeyboard);
@@ -984,7 +1053,7 @@ macro_rules! decl_builder_setter_vector2 {
 
 macro_rules! decl_builder_with_maybe_opt {
     ( $always_run_end:literal
-      $sname:ident, $func_beg:ident, $func_end:ident ($($life:lifetime),*) ( $( $gen_n:ident : $gen_d:tt ),* )
+      $sname:ident, $func_beg:ident, $func_end:ident $([ $($end_arg:ident = $end_pass:expr),* ])? ($($life:lifetime),*) ( $( $gen_n:ident : $gen_d:tt ),* )
         (
             $(
                 $arg:ident ($($ty:tt)*) ($pass:expr),
@@ -1028,15 +1097,18 @@ macro_rules! decl_builder_with_maybe_opt {
                     let _guard = push_guard(&push);
                     $func_beg($($pass,)*)
                 };
-                struct EndGuard(bool);
-                impl Drop for EndGuard {
+                // The end function may take some arguments, evaluated right after the begin function
+                $($(let $end_arg = $end_pass;)*)?
+                struct EndGuard<F: FnOnce()>(Option<F>);
+                impl<F: FnOnce()> Drop for EndGuard<F> {
                     fn drop(&mut self) {
-                        if self.0 {
-                            unsafe { $func_end(); }
+                        if let Some(end) = self.0.take() {
+                            end();
                         }
                     }
                 }
-                let _guard_2 = EndGuard($always_run_end || bres);
+                let end = move || unsafe { $func_end($($($end_arg),*)?); };
+                let _guard_2 = EndGuard(($always_run_end || bres).then_some(end));
                 f(bres)
             }
             $($extra)*
@@ -1082,6 +1154,15 @@ decl_builder_with! {Child, ImGui_BeginChild, ImGui_EndChild () (S: IntoCStr)
                 push: (),
             }
         }
+        /// Like `child_config` but with the boxed look of the old `BeginChildFrame`.
+        ///
+        /// It sets `ChildFlags::FrameStyle`, so it uses the `FrameBg` color and the frame rounding,
+        /// border and padding.
+        pub fn child_frame_config<S: IntoCStr>(&self, name: LblId<S>, size: Vector2) -> Child<S> {
+            self.child_config(name)
+                .size(size)
+                .child_flags(ChildFlags::FrameStyle)
+        }
     }
 }
 
@@ -1141,6 +1222,50 @@ decl_builder! { MenuItem -> bool, ImGui_MenuItem () (S1: IntoCStr, S2: IntoCStr)
     }
 }
 
+/// The state of an item just after building it.
+///
+/// It is returned by the `build_response` functions of some builders, to avoid calling
+/// `is_item_hovered`, `get_item_rect_min` and so on after each widget.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ItemResponse {
+    /// The item was clicked, this is what `build` returns.
+    pub clicked: bool,
+    /// The item is hovered.
+    pub hovered: bool,
+    /// The item is active, such as a button being held.
+    pub active: bool,
+    /// The upper-left corner of the item, in screen coordinates.
+    pub rect_min: Vector2,
+    /// The lower-right corner of the item, in screen coordinates.
+    pub rect_max: Vector2,
+}
+
+impl ItemResponse {
+    unsafe fn last_item(clicked: bool, hovered_flags: HoveredFlags) -> ItemResponse {
+        ItemResponse {
+            clicked,
+            hovered: ImGui_IsItemHovered(hovered_flags.bits()),
+            active: ImGui_IsItemActive(),
+            rect_min: im_to_v2(ImGui_GetItemRectMin()),
+            rect_max: im_to_v2(ImGui_GetItemRectMax()),
+        }
+    }
+}
+
+macro_rules! decl_builder_response {
+    () => {
+        /// Builds the widget and returns the state of the resulting item.
+        pub fn build_response(self) -> ItemResponse {
+            self.build_response_ex(HoveredFlags::None)
+        }
+        /// Like `build_response`, but with custom flags for the `hovered` test.
+        pub fn build_response_ex(self, hovered_flags: HoveredFlags) -> ItemResponse {
+            let clicked = self.build();
+            unsafe { ItemResponse::last_item(clicked, hovered_flags) }
+        }
+    };
+}
+
 decl_builder! { Button -> bool, ImGui_Button () (S: IntoCStr)
     (
         label (S::Temp) (label.as_ptr()),
@@ -1148,6 +1273,7 @@ decl_builder! { Button -> bool, ImGui_Button () (S: IntoCStr)
     )
     {
         decl_builder_setter_vector2!{size: Vector2}
+        decl_builder_response!{}
     }
     {
         pub fn button_config<S: IntoCStr>(&self, label: LblId<S>) -> Button<S> {
@@ -1166,7 +1292,9 @@ decl_builder! { SmallButton -> bool, ImGui_SmallButton () (S: IntoCStr)
     (
         label (S::Temp) (label.as_ptr()),
     )
-    {}
+    {
+        decl_builder_response!{}
+    }
     {
         pub fn small_button_config<S: IntoCStr>(&self, label: LblId<S>) -> SmallButton<S> {
             SmallButton {
@@ -1188,6 +1316,7 @@ decl_builder! { InvisibleButton -> bool, ImGui_InvisibleButton () (S: IntoCStr)
     {
         decl_builder_setter_vector2!{size: Vector2}
         decl_builder_setter!{flags: ButtonFlags}
+        decl_builder_response!{}
     }
     {
         pub fn invisible_button_config<S: IntoCStr>(&self, id: S) -> InvisibleButton<S> {
@@ -1205,7 +1334,9 @@ decl_builder! { ArrowButton -> bool, ImGui_ArrowButton () (S: IntoCStr)
         id (S::Temp) (id.as_ptr()),
         dir (Dir) (dir.bits()),
     )
-    {}
+    {
+        decl_builder_response!{}
+    }
     {
         pub fn arrow_button_config<S: IntoCStr>(&self, id: S, dir: Dir) -> ArrowButton<S> {
             ArrowButton {
@@ -1254,19 +1385,41 @@ decl_builder! { RadioButton -> bool, ImGui_RadioButton () (S: IntoCStr)
     }
 }
 
-decl_builder! { ProgressBar -> (), ImGui_ProgressBar () (S: IntoCStr)
+unsafe fn progress_bar_wrapper(
+    fraction: f32,
+    size: &ImVec2,
+    overlay: *const c_char,
+    indeterminate: bool,
+) {
+    // A negative fraction, changing over time, is animated as an indeterminate bar
+    let fraction = if indeterminate {
+        -(ImGui_GetTime() as f32)
+    } else {
+        fraction
+    };
+    ImGui_ProgressBar(fraction, size, overlay);
+}
+
+decl_builder! { ProgressBar -> (), progress_bar_wrapper () (S: IntoCStr)
     (
         fraction (f32) (fraction),
         size (ImVec2) (&size),
         overlay (Option<S::Temp>) (optional_str(&overlay)),
+        indeterminate (bool) (indeterminate),
     )
     {
         decl_builder_setter_vector2!{size: Vector2}
+        /// If set, the `fraction` is ignored and a looping animated bar is shown instead.
+        pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+            self.indeterminate = indeterminate;
+            self
+        }
         pub fn overlay<S2: IntoCStr>(self, overlay: S2) -> ProgressBar<S2> {
             ProgressBar {
                 fraction: self.fraction,
                 size: self.size,
                 overlay: Some(overlay.into()),
+                indeterminate: self.indeterminate,
             }
         }
     }
@@ -1276,12 +1429,66 @@ decl_builder! { ProgressBar -> (), ImGui_ProgressBar () (S: IntoCStr)
                 fraction,
                 size: im_vec2(-f32::MIN_POSITIVE, 0.0),
                 overlay: None,
+                indeterminate: false,
             }
         }
     }
 }
 
-decl_builder! { Image -> (), ImGui_Image () ()
+#[allow(clippy::too_many_arguments)]
+unsafe fn image_wrapper(
+    user_texture_id: ImTextureID,
+    size: &ImVec2,
+    uv0: &ImVec2,
+    uv1: &ImVec2,
+    tint_col: &ImVec4,
+    border_col: &ImVec4,
+    rounding: f32,
+    draw_flags: DrawFlags,
+) {
+    if rounding <= 0.0 {
+        ImGui_Image(user_texture_id, size, uv0, uv1, tint_col, border_col);
+        return;
+    }
+    // Same layout as `ImGui::Image`, but drawn with rounded corners
+    let border = if border_col.w > 0.0 { 1.0 } else { 0.0 };
+    ImGui_Dummy(&im_vec2(size.x + 2.0 * border, size.y + 2.0 * border));
+    if !ImGui_IsItemVisible() {
+        return;
+    }
+    let alpha = (*ImGui_GetStyle()).Alpha;
+    let min = im_to_v2(ImGui_GetItemRectMin());
+    let max = im_to_v2(ImGui_GetItemRectMax());
+    let draw_list = ImGui_GetWindowDrawList();
+    if border > 0.0 {
+        let mut col = Color::from(*border_col);
+        col.a *= alpha;
+        ImDrawList_AddRect(
+            draw_list,
+            &v2_to_im(min),
+            &v2_to_im(max),
+            col.as_u32(),
+            rounding,
+            draw_flags.bits(),
+            1.0,
+        );
+    }
+    let mut col = Color::from(*tint_col);
+    col.a *= alpha;
+    ImDrawList_AddImageRounded(
+        draw_list,
+        user_texture_id,
+        &im_vec2(min.x + border, min.y + border),
+        &im_vec2(max.x - border, max.y - border),
+        uv0,
+        uv1,
+        col.as_u32(),
+        rounding,
+        draw_flags.bits(),
+    );
+}
+
+decl_builder! { Image -> (), image_wrapper () ()
     (
         user_texture_id (TextureId) (user_texture_id.id()),
         size (ImVec2) (&size),
@@ -1289,12 +1496,24 @@ decl_builder! { Image -> (), ImGui_Image () ()
         uv1 (ImVec2) (&uv1),
         tint_col (ImVec4) (&tint_col),
         border_col (ImVec4) (&border_col),
+        rounding (f32) (rounding),
+        draw_flags (DrawFlags) (draw_flags),
     )
     {
         decl_builder_setter_vector2!{uv0: Vector2}
         decl_builder_setter_vector2!{uv1: Vector2}
         decl_builder_setter!{tint_col: Color}
         decl_builder_setter!{border_col: Color}
+        /// Draws the image with rounded corners, if greater than 0.
+        pub fn rounding(mut self, rounding: f32) -> Self {
+            self.rounding = rounding;
+            self
+        }
+        /// Selects the corners to be rounded, only used with `rounding`.
+        pub fn draw_flags(mut self, draw_flags: DrawFlags) -> Self {
+            self.draw_flags = draw_flags;
+            self
+        }
     }
     {
         pub fn image_config(&self, user_texture_id: TextureId, size: Vector2) -> Image {
@@ -1305,6 +1524,8 @@ decl_builder! { Image -> (), ImGui_Image () ()
                 uv1: im_vec2(1.0, 1.0),
                 tint_col: Color::WHITE.into(),
                 border_col: Color::TRANSPARENT.into(),
+                rounding: 0.0,
+                draw_flags: DrawFlags::RoundCornersAll,
             }
         }
         pub fn image_with_custom_rect_config(&self, ridx: CustomRectIndex, scale: f32) -> Image {
@@ -1339,6 +1560,7 @@ decl_builder! { ImageButton -> bool, ImGui_ImageButton () (S: IntoCStr)
         decl_builder_setter_vector2!{uv1: Vector2}
         decl_builder_setter!{bg_col: Color}
         decl_builder_setter!{tint_col: Color}
+        decl_builder_response!{}
     }
     {
         pub fn image_button_config<S: IntoCStr>(&self, str_id: Id<S>, user_texture_id: TextureId, size: Vector2) -> ImageButton<S> {
@@ -1463,6 +1685,13 @@ impl_float_format! { DragFloat2 }
 impl_float_format! { DragFloat3 }
 impl_float_format! { DragFloat4 }
 
+// Both `cgmath` and `mint` vectors are `repr(C)`, so they are layout compatible with a float array
+decl_builder_drag! { DragVec2 drag_vec2_config ImGui_DragFloat2 'v (f32) (&'v mut Vector2) (|v: &mut Vector2| v as *mut Vector2 as *mut f32)}
+decl_builder_drag! { DragVec3 drag_vec3_config ImGui_DragFloat3 'v (f32) (&'v mut mint::Vector3<f32>) (|v: &mut mint::Vector3<f32>| v as *mut mint::Vector3<f32> as *mut f32)}
+
+impl_float_format! { DragVec2 }
+impl_float_format! { DragVec3 }
+
 decl_builder_drag! { DragInt drag_int_config ImGui_DragInt 'v (i32) (&'v mut i32) (std::convert::identity)}
 decl_builder_drag! { DragInt2 drag_int_2_config ImGui_DragInt2 'v (i32) (&'v mut [i32; 2]) (<[i32]>::as_mut_ptr)}
 decl_builder_drag! { DragInt3 drag_int_3_config ImGui_DragInt3 'v (i32) (&'v mut [i32; 3]) (<[i32]>::as_mut_ptr)}
@@ -1637,10 +1866,38 @@ decl_builder! { ColorPicker4 -> bool, ImGui_ColorPicker4 ('v) (S: IntoCStr)
 unsafe extern "C" fn input_text_callback(data: *mut ImGuiInputTextCallbackData) -> i32 {
     let data = &mut *data;
     if data.EventFlag == InputTextFlags::CallbackResize.bits() {
-        let this = &mut *(data.UserData as *mut String);
-        let extra = (data.BufSize as usize).saturating_sub(this.len());
-        this.reserve(extra);
-        data.Buf = this.as_mut_ptr() as *mut c_char;
+        input_text_resize(data, &mut *(data.UserData as *mut String));
+    }
+    0
+}
+
+unsafe fn input_text_resize(data: &mut ImGuiInputTextCallbackData, text: &mut String) {
+    let extra = (data.BufSize as usize).saturating_sub(text.len());
+    text.reserve(extra);
+    data.Buf = text.as_mut_ptr() as *mut c_char;
+}
+
+/// The user data for `input_text_limited_callback`.
+struct InputTextLimit {
+    text: *mut String,
+    max_length: usize,
+}
+
+unsafe extern "C" fn input_text_limited_callback(data: *mut ImGuiInputTextCallbackData) -> i32 {
+    let data = &mut *data;
+    let limit = &*(data.UserData as *const InputTextLimit);
+    if data.EventFlag == InputTextFlags::CallbackResize.bits() {
+        input_text_resize(data, &mut *limit.text);
+    } else if data.EventFlag == InputTextFlags::CallbackEdit.bits() {
+        let buf = std::slice::from_raw_parts(data.Buf as *const u8, data.BufTextLen as usize);
+        // The buffer is always valid UTF-8, so the characters can be counted
+        let cut = std::str::from_utf8_unchecked(buf)
+            .char_indices()
+            .nth(limit.max_length);
+        if let Some((pos, _)) = cut {
+            let pos = pos as i32;
+            ImGuiInputTextCallbackData_DeleteChars(data, pos, data.BufTextLen - pos);
+        }
     }
     0
 }
@@ -1681,14 +1938,64 @@ unsafe fn input_text_wrapper(
     r
 }
 
-decl_builder! { InputText -> bool, input_text_wrapper ('v) (S: IntoCStr)
+unsafe fn input_text_limited_wrapper(
+    label: *const c_char,
+    text: &mut String,
+    flags: InputTextFlags,
+    max_length: Option<usize>,
+    show_count: bool,
+) -> bool {
+    let Some(max_length) = max_length else {
+        return input_text_wrapper(label, text, flags);
+    };
+    let flags = flags | InputTextFlags::CallbackResize | InputTextFlags::CallbackEdit;
+
+    // The initial text may be already too long
+    let mut changed = false;
+    if let Some((pos, _)) = text.char_indices().nth(max_length) {
+        text.truncate(pos);
+        changed = true;
+    }
+    text_pre_edit(text);
+    let buf = text.as_mut_ptr() as *mut c_char;
+    let buf_size = text.capacity();
+    let limit = InputTextLimit { text, max_length };
+    let r = ImGui_InputText(
+        label,
+        buf,
+        buf_size,
+        flags.bits(),
+        Some(input_text_limited_callback),
+        &limit as *const InputTextLimit as *mut c_void,
+    );
+    text_post_edit(text);
+    if show_count {
+        let count = IntoCStr::into(format!("{}/{}", text.chars().count(), max_length));
+        ImGui_TextDisabled(c"%s".as_ptr(), count.as_ptr());
+    }
+    r || changed
+}
+
+decl_builder! { InputText -> bool, input_text_limited_wrapper ('v) (S: IntoCStr)
     (
         label (S::Temp) (label.as_ptr()),
         text (&'v mut String) (text),
         flags (InputTextFlags) (flags),
+        max_length (Option<usize>) (max_length),
+        show_count (bool) (show_count),
     )
     {
         decl_builder_setter!{flags: InputTextFlags}
+        /// Limits the text to `max_length` characters, the rest are truncated.
+        pub fn max_length(mut self, max_length: usize) -> Self {
+            self.max_length = Some(max_length);
+            self
+        }
+        /// Shows a "length/max_length" counter below the input, only used with `max_length`.
+        pub fn show_count(mut self, show_count: bool) -> Self {
+            self.show_count = show_count;
+            self
+        }
     }
     {
         pub fn input_text_config<'v, S: IntoCStr>(&self, label: LblId<S>, text: &'v mut String) -> InputText<'v, S> {
@@ -1696,6 +2003,65 @@ decl_builder! { InputText -> bool, input_text_wrapper ('v) (S: IntoCStr)
                 label: label.into(),
                 text,
                 flags: InputTextFlags::None,
+                max_length: None,
+                show_count: false,
+            }
+        }
+        /// Like `input_text_config` but for a password, the text is shown masked.
+        ///
+        /// It also disables copying the text and the undo history, so the password is not kept
+        /// around.
+        pub fn input_password_config<'v, S: IntoCStr>(&self, label: LblId<S>, text: &'v mut String) -> InputText<'v, S> {
+            self.input_text_config(label, text)
+                .flags(InputTextFlags::Password | InputTextFlags::NoUndoRedo)
+        }
+    }
+}
+
+unsafe fn input_text_fixed_wrapper(
+    label: *const c_char,
+    buf: &mut [u8],
+    flags: InputTextFlags,
+) -> bool {
+    let Some(last) = buf.last_mut() else {
+        return false;
+    };
+    // The text must be NUL terminated inside the buffer, if not it is truncated
+    let mut changed = false;
+    if !buf.contains(&0) {
+        *last = 0;
+        changed = true;
+    }
+    let r = ImGui_InputText(
+        label,
+        buf.as_mut_ptr() as *mut c_char,
+        buf.len(),
+        flags.bits(),
+        None,
+        null_mut(),
+    );
+    r || changed
+}
+
+decl_builder! { InputTextFixed -> bool, input_text_fixed_wrapper ('v) (S: IntoCStr)
+    (
+        label (S::Temp) (label.as_ptr()),
+        buf (&'v mut [u8]) (buf),
+        flags (InputTextFlags) (flags),
+    )
+    {
+        decl_builder_setter!{flags: InputTextFlags}
+    }
+    {
+        /// Like `input_text_config` but edits the text in place, inside a fixed size buffer.
+        ///
+        /// The text is NUL terminated, so it can hold up to `buf.len() - 1` bytes, any further
+        /// input is discarded. Use `CStr::from_bytes_until_nul` to get the text back.
+        pub fn input_text_fixed_config<'v, S: IntoCStr>(&self, label: LblId<S>, buf: &'v mut [u8]) -> InputTextFixed<'v, S> {
+            InputTextFixed {
+                label: label.into(),
+                buf,
+                flags: InputTextFlags::None,
             }
         }
     }
@@ -1736,37 +2102,127 @@ decl_builder! { InputOsString -> bool, input_os_string_wrapper ('v) (S: IntoCStr
     }
 }
 
+/// A function that splits a text into colored spans, see [`InputTextMultiline::highlight`].
+type HighlightFn<'v> = Box<dyn Fn(&str) -> Vec<(Range<usize>, Color)> + 'v>;
+
 unsafe fn input_text_multiline_wrapper(
     label: *const c_char,
     text: &mut String,
     size: &ImVec2,
     flags: InputTextFlags,
+    highlight: Option<HighlightFn<'_>>,
 ) -> bool {
     let flags = flags | InputTextFlags::CallbackResize;
     text_pre_edit(text);
-    let r = ImGui_InputTextMultiline(
-        label,
-        text.as_mut_ptr() as *mut c_char,
-        text.capacity(),
-        size,
-        flags.bits(),
-        Some(input_text_callback),
-        text as *mut String as *mut c_void,
-    );
+    let r = {
+        // If highlighting, the real text is drawn transparent, and then overdrawn
+        let hide = highlight
+            .as_ref()
+            .map(|_| (ColorId::Text, Color::TRANSPARENT));
+        let _guard = push_guard(&hide);
+        ImGui_InputTextMultiline(
+            label,
+            text.as_mut_ptr() as *mut c_char,
+            text.capacity(),
+            size,
+            flags.bits(),
+            Some(input_text_callback),
+            text as *mut String as *mut c_void,
+        )
+    };
     text_post_edit(text);
+    if let Some(highlight) = highlight {
+        let spans = highlight(text);
+        draw_text_highlight(label, text, spans);
+    }
     r
 }
 
+/// Draws the text of the multiline input text that has just been submitted, with the given colors.
+unsafe fn draw_text_highlight(
+    label: *const c_char,
+    text: &str,
+    mut spans: Vec<(Range<usize>, Color)>,
+) {
+    let ctx = &*ImGui_GetCurrentContext();
+    let window = &*ctx.CurrentWindow;
+    // The multiline input is a child window, named as `BeginChildEx` does, with the ID of the item
+    let mut name = CStr::from_ptr(window.Name).to_bytes().to_vec();
+    name.push(b'/');
+    name.extend_from_slice(CStr::from_ptr(label).to_bytes());
+    name.extend_from_slice(format!("_{:08X}\0", ImGui_GetItemID()).as_bytes());
+    let child = ImGui_FindWindowByName(name.as_ptr() as *const c_char);
+    if child.is_null() || (*child).SkipItems {
+        return;
+    }
+    let child = &*child;
+    let draw_list = child.DrawList;
+    let origin = im_to_v2(child.Pos) + im_to_v2(ctx.Style.FramePadding) - im_to_v2(child.Scroll);
+    let line_height = ctx.FontSize;
+    let mut default_color = Color::from(ctx.Style.Colors[ColorId::Text.bits() as usize]);
+    default_color.a *= ctx.Style.Alpha;
+    let default_color = default_color.as_u32();
+
+    spans.sort_by_key(|(r, _)| r.start);
+
+    let draw = |line_start: usize, start: usize, end: usize, y: f32, color: u32| {
+        if start >= end || !text.is_char_boundary(start) || !text.is_char_boundary(end) {
+            return;
+        }
+        let (p0, p1) = text_ptrs(&text[line_start..start]);
+        let x = origin.x + im_to_v2(ImGui_CalcTextSize(p0, p1, false, -1.0)).x;
+        let (p0, p1) = text_ptrs(&text[start..end]);
+        ImDrawList_AddText(draw_list, &im_vec2(x, y), color, p0, p1);
+    };
+
+    ImDrawList_PushClipRect(
+        draw_list,
+        &child.InnerClipRect.Min,
+        &child.InnerClipRect.Max,
+        true,
+    );
+    let mut line_start = 0;
+    let mut y = origin.y;
+    for line in text.split('\n') {
+        let line_end = line_start + line.len();
+        let mut pos = line_start;
+        for (range, color) in &spans {
+            let start = range.start.clamp(pos, line_end);
+            let end = range.end.clamp(start, line_end);
+            if start == end {
+                continue;
+            }
+            draw(line_start, pos, start, y, default_color);
+            draw(line_start, start, end, y, color.as_u32());
+            pos = end;
+        }
+        draw(line_start, pos, line_end, y, default_color);
+        line_start = line_end + 1;
+        y += line_height;
+    }
+    ImDrawList_PopClipRect(draw_list);
+}
+
 decl_builder! { InputTextMultiline -> bool, input_text_multiline_wrapper ('v) (S: IntoCStr)
     (
         label (S::Temp) (label.as_ptr()),
         text (&'v mut String) (text),
         size (ImVec2) (&size),
         flags (InputTextFlags) (flags),
+        highlight (Option<HighlightFn<'v>>) (highlight),
     )
     {
         decl_builder_setter!{flags: InputTextFlags}
         decl_builder_setter_vector2!{size: Vector2}
+        /// Sets a function to colorize the text, for simple syntax highlighting.
+        ///
+        /// It is called with the current text and returns a list of byte ranges with their colors.
+        /// Text not covered by any range is drawn with the `ColorId::Text` color.
+        /// The label, if visible, will be drawn transparent, so better use a `##` hidden label.
+        pub fn highlight(mut self, highlight: impl Fn(&str) -> Vec<(Range<usize>, Color)> + 'v) -> Self {
+            self.highlight = Some(Box::new(highlight));
+            self
+        }
     }
     {
         pub fn input_text_multiline_config<'v, S: IntoCStr>(&self, label: LblId<S>, text: &'v mut String) -> InputTextMultiline<'v, S> {
@@ -1775,6 +2231,7 @@ decl_builder! { InputTextMultiline -> bool, input_text_multiline_wrapper ('v) (S
                 text,
                 flags: InputTextFlags::None,
                 size: im_vec2(0.0, 0.0),
+                highlight: None,
             }
         }
     }
@@ -1833,7 +2290,7 @@ pub enum FloatFormat {
     G,
 }
 
-decl_builder! { InputFloat -> bool, ImGui_InputFloat ('v) (S: IntoCStr)
+decl_builder! { InputFloat -> bool, input_float_wrapper ('v) (S: IntoCStr)
     (
         label (S::Temp)  (label.as_ptr()),
         value (&'v mut f32) (value),
@@ -1841,11 +2298,19 @@ decl_builder! { InputFloat -> bool, ImGui_InputFloat ('v) (S: IntoCStr)
         step_fast (f32) (step_fast),
         format (Cow<'static, CStr>) (format.as_ptr()),
         flags (InputTextFlags) (flags.bits()),
+        range (Option<(f32, f32)>) (range),
     )
     {
         decl_builder_setter!{flags: InputTextFlags}
         decl_builder_setter!{step: f32}
         decl_builder_setter!{step_fast: f32}
+        /// Clamps the value to `min..=max` when the user finishes editing it.
+        ///
+        /// While typing, the value can be out of range.
+        pub fn clamp(mut self, min: f32, max: f32) -> Self {
+            self.range = Some((min, max));
+            self
+        }
     }
     {
         pub fn input_float_config<'v, S: IntoCStr>(&self, label: LblId<S>, value: &'v mut f32) -> InputFloat<'v, S> {
@@ -1856,23 +2321,32 @@ decl_builder! { InputFloat -> bool, ImGui_InputFloat ('v) (S: IntoCStr)
                 step_fast: 0.0,
                 format: Cow::Borrowed(c"%.3f"),
                 flags: InputTextFlags::None,
+                range: None,
             }
         }
     }
 }
 
-decl_builder! { InputInt -> bool, ImGui_InputInt ('v) (S: IntoCStr)
+decl_builder! { InputInt -> bool, input_int_wrapper ('v) (S: IntoCStr)
     (
         label (S::Temp) (label.as_ptr()),
         value (&'v mut i32) (value),
         step (i32) (step),
         step_fast (i32) (step_fast),
         flags (InputTextFlags) (flags.bits()),
+        range (Option<(i32, i32)>) (range),
     )
     {
         decl_builder_setter!{flags: InputTextFlags}
         decl_builder_setter!{step: i32}
         decl_builder_setter!{step_fast: i32}
+        /// Clamps the value to `min..=max` when the user finishes editing it.
+        ///
+        /// While typing, the value can be out of range.
+        pub fn clamp(mut self, min: i32, max: i32) -> Self {
+            self.range = Some((min, max));
+            self
+        }
     }
     {
         pub fn input_int_config<'v, S: IntoCStr>(&self, label: LblId<S>, value: &'v mut i32) -> InputInt<'v, S> {
@@ -1882,11 +2356,109 @@ decl_builder! { InputInt -> bool, ImGui_InputInt ('v) (S: IntoCStr)
                 step: 1,
                 step_fast: 100,
                 flags: InputTextFlags::None,
+                range: None,
+            }
+        }
+    }
+}
+
+decl_builder! { InputDouble -> bool, input_double_wrapper ('v) (S: IntoCStr)
+    (
+        label (S::Temp)  (label.as_ptr()),
+        value (&'v mut f64) (value),
+        step (f64) (step),
+        step_fast (f64) (step_fast),
+        format (Cow<'static, CStr>) (format.as_ptr()),
+        flags (InputTextFlags) (flags.bits()),
+        range (Option<(f64, f64)>) (range),
+    )
+    {
+        decl_builder_setter!{flags: InputTextFlags}
+        decl_builder_setter!{step: f64}
+        decl_builder_setter!{step_fast: f64}
+        /// Clamps the value to `min..=max` when the user finishes editing it.
+        ///
+        /// While typing, the value can be out of range.
+        pub fn clamp(mut self, min: f64, max: f64) -> Self {
+            self.range = Some((min, max));
+            self
+        }
+    }
+    {
+        pub fn input_double_config<'v, S: IntoCStr>(&self, label: LblId<S>, value: &'v mut f64) -> InputDouble<'v, S> {
+            InputDouble {
+                label: label.into(),
+                value,
+                step: 0.0,
+                step_fast: 0.0,
+                format: Cow::Borrowed(c"%.6f"),
+                flags: InputTextFlags::None,
+                range: None,
             }
         }
     }
 }
 
+// Clamps `value` to `range`, but only if the item is not being edited.
+unsafe fn input_clamp_last_item<T: PartialOrd + Copy>(
+    value: &mut T,
+    range: Option<(T, T)>,
+) -> bool {
+    let Some((min, max)) = range else {
+        return false;
+    };
+    if ImGui_IsItemActive() {
+        return false;
+    }
+    let clamped = if *value < min {
+        min
+    } else if *value > max {
+        max
+    } else {
+        return false;
+    };
+    *value = clamped;
+    true
+}
+
+unsafe fn input_float_wrapper(
+    label: *const c_char,
+    value: &mut f32,
+    step: f32,
+    step_fast: f32,
+    format: *const c_char,
+    flags: ImGuiInputTextFlags,
+    range: Option<(f32, f32)>,
+) -> bool {
+    let changed = ImGui_InputFloat(label, value, step, step_fast, format, flags);
+    input_clamp_last_item(value, range) || changed
+}
+
+unsafe fn input_double_wrapper(
+    label: *const c_char,
+    value: &mut f64,
+    step: f64,
+    step_fast: f64,
+    format: *const c_char,
+    flags: ImGuiInputTextFlags,
+    range: Option<(f64, f64)>,
+) -> bool {
+    let changed = ImGui_InputDouble(label, value, step, step_fast, format, flags);
+    input_clamp_last_item(value, range) || changed
+}
+
+unsafe fn input_int_wrapper(
+    label: *const c_char,
+    value: &mut i32,
+    step: i32,
+    step_fast: i32,
+    flags: ImGuiInputTextFlags,
+    range: Option<(i32, i32)>,
+) -> bool {
+    let changed = ImGui_InputInt(label, value, step, step_fast, flags);
+    input_clamp_last_item(value, range) || changed
+}
+
 macro_rules! decl_builder_input_f {
     ($name:ident $func:ident $cfunc:ident $len:literal) => {
         decl_builder! { $name -> bool, $cfunc ('v) (S: IntoCStr)
@@ -1919,6 +2491,7 @@ decl_builder_input_f! { InputFloat3 input_float_3_config ImGui_InputFloat3 3}
 decl_builder_input_f! { InputFloat4 input_float_4_config ImGui_InputFloat4 4}
 
 impl_float_format! { InputFloat }
+impl_float_format! { InputDouble }
 impl_float_format! { InputFloat2 }
 impl_float_format! { InputFloat3 }
 impl_float_format! { InputFloat4 }
@@ -2035,7 +2608,34 @@ decl_builder_with_opt! {TreeNode, tree_node_ex_helper, ImGui_TreePop ('a) (S: In
     }
 }
 
-decl_builder_with_opt! {Popup, ImGui_BeginPopup, ImGui_EndPopup () (S: IntoCStr)
+/// Gets the depth of the popup stack, to check it when the popup ends.
+fn popup_stack_size() -> i32 {
+    unsafe { (*ImGui_GetCurrentContext()).BeginPopupStack.Size }
+}
+
+/// Ends the popup begun at this `depth` of the popup stack.
+///
+/// If the inner code left some other popups open, they are ended too, so that the stack is
+/// consistent again. In debug builds that is also reported with a panic, instead of a confusing
+/// Dear ImGui assertion later.
+unsafe fn popup_end_wrapper(depth: i32) {
+    let extra = popup_stack_size() - depth;
+    for _ in 0..=extra {
+        ImGui_EndPopup();
+    }
+    if cfg!(debug_assertions) && extra != 0 && !std::thread::panicking() {
+        if extra > 0 {
+            panic!("unbalanced popup stack: {extra} popup(s) begun but not ended");
+        } else {
+            panic!(
+                "unbalanced popup stack: {} popup(s) ended that were not begun",
+                -extra
+            );
+        }
+    }
+}
+
+decl_builder_with_opt! {Popup, ImGui_BeginPopup, popup_end_wrapper [depth = popup_stack_size()] () (S: IntoCStr)
     (
         str_id (S::Temp) (str_id.as_ptr()),
         flags (WindowFlags) (flags.bits()),
@@ -2070,7 +2670,7 @@ impl PopupOpened<'_> {
     }
 }
 
-decl_builder_with_opt! {PopupModal, ImGui_BeginPopupModal, ImGui_EndPopup ('a) (S: IntoCStr)
+decl_builder_with_opt! {PopupModal, ImGui_BeginPopupModal, popup_end_wrapper [depth = popup_stack_size()] ('a) (S: IntoCStr)
     (
         name (S::Temp) (name.as_ptr()),
         opened (PopupOpened<'a>) (opened.pointer()),
@@ -2109,7 +2709,7 @@ decl_builder_with_opt! {PopupModal, ImGui_BeginPopupModal, ImGui_EndPopup ('a) (
 
 macro_rules! decl_builder_popup_context {
     ($struct:ident $begin:ident $do_function:ident) => {
-        decl_builder_with_opt! {$struct, $begin, ImGui_EndPopup () (S: IntoCStr)
+        decl_builder_with_opt! {$struct, $begin, popup_end_wrapper [depth = popup_stack_size()] () (S: IntoCStr)
             (
                 str_id (Option<S::Temp>) (optional_str(&str_id)),
                 flags (PopupFlags) (flags.bits()),
@@ -2138,7 +2738,81 @@ macro_rules! decl_builder_popup_context {
     };
 }
 
-decl_builder_popup_context! {PopupContextItem ImGui_BeginPopupContextItem popup_context_item_config}
+decl_builder_with_opt! {PopupContextItem, popup_context_item_wrapper, popup_end_wrapper [depth = popup_stack_size()] () (S: IntoCStr)
+    (
+        str_id (Option<S::Temp>) (optional_str(&str_id)),
+        item_id (Option<ImGuiID>) (item_id),
+        flags (PopupFlags) (flags.bits()),
+    )
+    {
+        decl_builder_setter!{flags: PopupFlags}
+        pub fn str_id<S2: IntoCStr>(self, str_id: LblId<S2>) -> PopupContextItem<S2, P> {
+            PopupContextItem {
+                str_id: Some(str_id.into()),
+                item_id: self.item_id,
+                flags: self.flags,
+                push: self.push,
+            }
+        }
+        /// Opens the popup when the item with this ID is clicked, instead of the last item.
+        ///
+        /// The item must have been drawn before in this frame, and it must be hoverable,
+        /// such as a button or a selectable. Unless `str_id` is set, the popup uses the
+        /// same ID.
+        pub fn for_item_id(mut self, item_id: ImGuiID) -> Self {
+            self.item_id = Some(item_id);
+            self
+        }
+    }
+    {
+        pub fn popup_context_item_config<'a>(&self) -> PopupContextItem<&'a str> {
+            PopupContextItem {
+                str_id: None,
+                item_id: None,
+                flags: PopupFlags::MouseButtonRight,
+                push: (),
+            }
+        }
+    }
+}
+
+unsafe fn popup_context_item_wrapper(
+    str_id: *const c_char,
+    item_id: Option<ImGuiID>,
+    flags: ImGuiPopupFlags,
+) -> bool {
+    let Some(item_id) = item_id else {
+        return ImGui_BeginPopupContextItem(str_id, flags);
+    };
+    // Same as `BeginPopupContextItem` but for the given item instead of the last one
+    let g = &*ImGui_GetCurrentContext();
+    if (*g.CurrentWindow).SkipItems {
+        return false;
+    }
+    let id = if str_id.is_null() {
+        item_id
+    } else {
+        ImGui_GetID(str_id)
+    };
+    let hovered = if g.LastItemData.ID == item_id {
+        ImGui_IsItemHovered(HoveredFlags::AllowWhenBlockedByPopup.bits())
+    } else {
+        // Only the ID of an earlier item is known, so check the hovered ID. A popup that
+        // blocks the item is closed when the mouse is clicked, and the release comes in a
+        // later frame, so by then the item is no longer blocked.
+        g.HoveredId == item_id
+    };
+    let mouse_button = flags & PopupFlags::MouseButtonMask_.bits();
+    if ImGui_IsMouseReleased(mouse_button) && hovered {
+        ImGui_OpenPopupEx(id, flags);
+    }
+    ImGui_BeginPopupEx(
+        id,
+        (WindowFlags::AlwaysAutoResize | WindowFlags::NoTitleBar | WindowFlags::NoSavedSettings)
+            .bits(),
+    )
+}
+
 decl_builder_popup_context! {PopupContextWindow ImGui_BeginPopupContextWindow popup_context_window_config}
 decl_builder_popup_context! {PopupContextVoid ImGui_BeginPopupContextVoid popup_context_void_config}
 
@@ -2196,9 +2870,33 @@ decl_builder_with_opt! {Combo, ImGui_BeginCombo, ImGui_EndCombo () (S1: IntoCStr
                 });
             changed
         }
+        /// Shows a combo box to choose one of the values of a [`ComboItem`], usually an `enum`.
+        pub fn combo_enum<T: ComboItem, S: IntoCStr>(&self, label: LblId<S>, current: &mut T) -> bool {
+            self.combo(label, T::ALL.iter().copied(), |v| v.label(), current)
+        }
     }
 }
 
+/// A type that can be chosen in a combo box, see [`Ui::combo_enum`].
+///
+/// With the `derive` feature it can be derived for fieldless enums, the label being the name of
+/// the variant or that in a `#[combo(label = "...")]` attribute:
+///
+/// ```rust, ignore
+/// #[derive(Copy, Clone, PartialEq, easy_imgui::ComboItem)]
+/// enum Mode {
+///     Fast,
+///     #[combo(label = "Very slow")]
+///     Slow,
+/// }
+/// ```
+pub trait ComboItem: Copy + PartialEq + 'static {
+    /// All the values, in the order they are shown.
+    const ALL: &'static [Self];
+    /// The text shown for this value.
+    fn label(&self) -> &'static str;
+}
+
 decl_builder_with_opt! {ListBox, ImGui_BeginListBox, ImGui_EndListBox () (S: IntoCStr)
     (
         label (S::Temp) (label.as_ptr()),
@@ -2271,22 +2969,81 @@ decl_builder_with_opt! {TabBar, ImGui_BeginTabBar, ImGui_EndTabBar () (S: IntoCS
     }
 }
 
-decl_builder_with_opt! {TabItem, ImGui_BeginTabItem, ImGui_EndTabItem ('o) (S: IntoCStr)
+unsafe fn tab_item_wrapper(
+    str_id: *const c_char,
+    opened: Option<&mut bool>,
+    flags: ImGuiTabItemFlags,
+    icon: Option<char>,
+    on_close: Option<Box<dyn FnOnce() + '_>>,
+) -> bool {
+    // The icon is prepended to the label, keeping the original ID
+    let label;
+    let str_id = match icon {
+        Some(icon) => {
+            let id = CStr::from_ptr(str_id).to_bytes();
+            let mut bs = format!("{icon} ").into_bytes();
+            bs.extend_from_slice(id);
+            if !id.windows(3).any(|w| w == b"###") {
+                bs.extend_from_slice(b"###");
+                bs.extend_from_slice(id);
+            }
+            label = CString::new(bs).unwrap();
+            label.as_ptr()
+        }
+        None => str_id,
+    };
+    // With `on_close` there must be a close button, even without `opened`
+    let mut local_opened = true;
+    let p_opened: *mut bool = match opened {
+        Some(opened) => opened,
+        None if on_close.is_some() => &mut local_opened,
+        None => null_mut(),
+    };
+    let was_opened = !p_opened.is_null() && *p_opened;
+    let res = ImGui_BeginTabItem(str_id, p_opened, flags);
+    if was_opened && !*p_opened {
+        if let Some(on_close) = on_close {
+            on_close();
+        }
+    }
+    res
+}
+
+decl_builder_with_opt! {TabItem, tab_item_wrapper, ImGui_EndTabItem ('o) (S: IntoCStr)
     (
         str_id (S::Temp) (str_id.as_ptr()),
-        opened (Option<&'o mut bool>) (optional_mut_bool(&mut opened)),
+        opened (Option<&'o mut bool>) (opened),
         flags (TabItemFlags) (flags.bits()),
+        icon (Option<char>) (icon),
+        on_close (Option<Box<dyn FnOnce() + 'o>>) (on_close),
     )
     {
         decl_builder_setter!{flags: TabItemFlags}
         decl_builder_setter!{opened: &'o mut bool}
+        /// Draws an icon before the label, usually a glyph from an icon font.
+        ///
+        /// The ID of the tab does not change.
+        pub fn icon(mut self, icon: char) -> Self {
+            self.icon = Some(icon);
+            self
+        }
+        /// Calls `on_close` when the close button of the tab is clicked.
+        ///
+        /// It implies a close button, even without `opened`. Then, the tab is only hidden for
+        /// the current frame, so `on_close` should remove it.
+        pub fn on_close(mut self, on_close: impl FnOnce() + 'o) -> Self {
+            self.on_close = Some(Box::new(on_close));
+            self
+        }
     }
     {
-        pub fn tab_item_config<S: IntoCStr>(&self, str_id: LblId<S>) -> TabItem<S> {
+        pub fn tab_item_config<'o, S: IntoCStr>(&self, str_id: LblId<S>) -> TabItem<'o, S> {
             TabItem {
                 str_id: str_id.into(),
                 opened: None,
                 flags: TabItemFlags::None,
+                icon: None,
+                on_close: None,
                 push: (),
             }
         }
@@ -2381,6 +3138,17 @@ impl<A> Ui<A> {
             );
         }
     }
+    /// Constrains the size of the next window to keep `width / height == ratio`.
+    ///
+    /// The width is what the user changes, the height follows. Note that the size is
+    /// that of the whole window, including the title bar, if any.
+    pub fn set_next_window_aspect_ratio(&self, ratio: f32, size_min: Vector2, size_max: Vector2) {
+        self.set_next_window_size_constraints_callback(size_min, size_max, move |mut data| {
+            let mut sz = data.desired_size();
+            sz.y = (sz.x / ratio).round();
+            data.set_desired_size(sz);
+        });
+    }
     pub fn set_next_window_size_constraints(&self, size_min: Vector2, size_max: Vector2) {
         unsafe {
             ImGui_SetNextWindowSizeConstraints(
@@ -2391,11 +3159,58 @@ impl<A> Ui<A> {
             );
         }
     }
+    /// Sets a minimum size for the next window, `None` leaves that axis unconstrained.
+    ///
+    /// Note that the sentinel `-1.0` in `set_next_window_size_constraints` means to keep the
+    /// current size, not "no constraint", that is `0.0` for the minimum and `f32::MAX` for the
+    /// maximum.
+    pub fn set_next_window_min_size(&self, width: Option<f32>, height: Option<f32>) {
+        self.set_next_window_size_constraints(
+            Vector2::new(width.unwrap_or(0.0), height.unwrap_or(0.0)),
+            Vector2::new(f32::MAX, f32::MAX),
+        );
+    }
+    /// Sets a maximum size for the next window, `None` leaves that axis unconstrained.
+    pub fn set_next_window_max_size(&self, width: Option<f32>, height: Option<f32>) {
+        self.set_next_window_size_constraints(
+            Vector2::new(0.0, 0.0),
+            Vector2::new(width.unwrap_or(f32::MAX), height.unwrap_or(f32::MAX)),
+        );
+    }
     pub fn set_next_item_width(&self, item_width: f32) {
         unsafe {
             ImGui_SetNextItemWidth(item_width);
         }
     }
+    /// Allows the next item to be overlapped by later items.
+    ///
+    /// Without it, an item drawn over a previous one, such as a button on top of a list row,
+    /// would not receive the clicks, because the row takes them:
+    ///
+    /// ```rust, no_run
+    /// # use easy_imgui::{Ui, lbl};
+    /// # fn example(ui: &Ui<()>, names: &mut Vec<String>) {
+    /// let mut to_remove = None;
+    /// for (i, name) in names.iter().enumerate() {
+    ///     ui.with_id(i, || {
+    ///         ui.set_next_item_allow_overlap();
+    ///         ui.selectable_config(lbl(name)).build();
+    ///         ui.same_line_ex(ui.get_window_width() - 40.0, 0.0);
+    ///         if ui.small_button(lbl("X")) {
+    ///             to_remove = Some(i);
+    ///         }
+    ///     });
+    /// }
+    /// if let Some(i) = to_remove {
+    ///     names.remove(i);
+    /// }
+    /// # }
+    /// ```
+    pub fn set_next_item_allow_overlap(&self) {
+        unsafe {
+            ImGui_SetNextItemAllowOverlap();
+        }
+    }
     pub fn set_next_item_open(&self, is_open: bool, cond: Cond) {
         unsafe {
             ImGui_SetNextItemOpen(is_open, cond.bits());
@@ -2428,6 +3243,18 @@ impl<A> Ui<A> {
         /// See `BeginMainMenuBar`, `EndMainMenuBar`.
         main_menu_bar ImGui_BeginMainMenuBar ImGui_EndMainMenuBar ()
     }
+    /// Gets the height of the main menu bar, or `0.0` if there is none.
+    ///
+    /// It is valid after `with_main_menu_bar`, or until then, the value of the previous frame.
+    /// Note that the work area of the main viewport already excludes the menu bar.
+    pub fn main_menu_bar_height(&self) -> f32 {
+        unsafe {
+            match ImGui_FindWindowByName(c"##MainMenuBar".as_ptr()).as_ref() {
+                Some(window) if window.Active || window.WasActive => window.Size.y,
+                _ => 0.0,
+            }
+        }
+    }
     with_begin_end_opt! {
         /// See `BeginMenuBar`, `EndMenuBar`.
         menu_bar ImGui_BeginMenuBar ImGui_EndMenuBar ()
@@ -2448,9 +3275,47 @@ impl<A> Ui<A> {
             f()
         }
     }
-    pub fn show_demo_window(&self, mut show: Option<&mut bool>) {
-        unsafe {
-            ImGui_ShowDemoWindow(optional_mut_bool(&mut show));
+    /// Calls the `f` functions with the given `id` pushed into the ID stack.
+    ///
+    /// It is the same as `with_push(ItemId(id), f)`, useful for lists of identical widgets:
+    ///
+    /// ```rust, no_run
+    /// # use easy_imgui::{Ui, lbl};
+    /// # fn example(ui: &Ui<()>, names: &mut Vec<String>) {
+    /// let mut to_remove = None;
+    /// for (i, name) in names.iter().enumerate() {
+    ///     ui.with_id(i, || {
+    ///         ui.text(name);
+    ///         ui.same_line();
+    ///         if ui.button(lbl("Remove")) {
+    ///             to_remove = Some(i);
+    ///         }
+    ///     });
+    /// }
+    /// if let Some(i) = to_remove {
+    ///     names.remove(i);
+    /// }
+    /// # }
+    /// ```
+    pub fn with_id<R>(&self, id: impl Hashable, f: impl FnOnce() -> R) -> R {
+        self.with_push(ItemId(id), f)
+    }
+    /// Calls the `f` functions with the given `font` pushed.
+    ///
+    /// It is the same as `with_push(font, f)`.
+    pub fn with_font<R>(&self, font: FontId, f: impl FnOnce() -> R) -> R {
+        self.with_push(font, f)
+    }
+    /// Calls the `f` functions with the given `font` pushed, scaled to `size` pixels.
+    ///
+    /// It is the same as `with_push(FontSize::new(font, size), f)`, see [`FontSize`] for the
+    /// caveats.
+    pub fn with_font_size<R>(&self, font: FontId, size: f32, f: impl FnOnce() -> R) -> R {
+        self.with_push(FontSize::new(font, size), f)
+    }
+    pub fn show_demo_window(&self, mut show: Option<&mut bool>) {
+        unsafe {
+            ImGui_ShowDemoWindow(optional_mut_bool(&mut show));
         }
     }
     pub fn set_next_window_pos(&self, pos: Vector2, cond: Cond, pivot: Vector2) {
@@ -2498,6 +3363,18 @@ impl<A> Ui<A> {
             WindowDrawList { ui: self, ptr }
         }
     }
+    /// Gets the current clip rectangle of the window, as `(min, max)` in screen coordinates.
+    ///
+    /// It is the same as `window_draw_list().clip_rect()`.
+    pub fn get_current_clip_rect(&self) -> (Vector2, Vector2) {
+        self.window_draw_list().clip_rect()
+    }
+    /// Gets the foreground draw list, that is drawn after all the windows.
+    ///
+    /// It is always on top of everything else, including popups, modal windows and their
+    /// dimmed background, and tooltips. It is cleared every frame.
+    ///
+    /// With the `docking` feature, this is the one of the viewport of the current window.
     pub fn foreground_draw_list(&self) -> WindowDrawList<'_, A> {
         unsafe {
             #[cfg(feature = "docking")]
@@ -2507,6 +3384,11 @@ impl<A> Ui<A> {
             WindowDrawList { ui: self, ptr }
         }
     }
+    /// Gets the background draw list, that is drawn before all the windows.
+    ///
+    /// It is always behind everything else. It is cleared every frame.
+    ///
+    /// With the `docking` feature, this is the one of the viewport of the current window.
     pub fn background_draw_list(&self) -> WindowDrawList<'_, A> {
         unsafe {
             #[cfg(feature = "docking")]
@@ -2522,6 +3404,15 @@ impl<A> Ui<A> {
             ImGui_TextUnformatted(start, end);
         }
     }
+    /// Shows many lines of text, but only the visible ones are actually submitted.
+    ///
+    /// Useful for very long logs. Every line must have the same height, so no embedded
+    /// newlines are allowed.
+    pub fn text_lines_clipped(&self, lines: &[&str]) {
+        self.list_clipper(lines.len())
+            .items_height(self.get_text_line_height_with_spacing())
+            .with(|i| self.text(lines[i]));
+    }
     pub fn text_colored(&self, color: Color, text: impl IntoCStr) {
         let text = text.into();
         unsafe { ImGui_TextColored(&color.into(), c"%s".as_ptr(), text.as_ptr()) }
@@ -2534,6 +3425,32 @@ impl<A> Ui<A> {
         let text = text.into();
         unsafe { ImGui_TextWrapped(c"%s".as_ptr(), text.as_ptr()) }
     }
+    /// Calls `f` with the text wrap position set to `wrap_pos_x`, see `PushTextWrapPos`.
+    ///
+    /// It is in window local coordinates: `0.0` wraps at the end of the window and a negative
+    /// value disables wrapping.
+    pub fn with_text_wrap_pos<R>(&self, wrap_pos_x: f32, f: impl FnOnce() -> R) -> R {
+        self.with_push(TextWrapPos(wrap_pos_x), f)
+    }
+    /// Shows a text wrapped to `width`, starting at the current cursor position.
+    pub fn text_wrapped_at(&self, width: f32, text: &str) {
+        let wrap_pos_x = self.get_cursor_pos().x + width;
+        self.with_text_wrap_pos(wrap_pos_x, || self.text(text));
+    }
+    /// Shows a simple text tooltip if the previous item is hovered.
+    ///
+    /// For more complex tooltips use `with_item_tooltip`.
+    pub fn set_item_tooltip(&self, text: impl IntoCStr) {
+        let text = text.into();
+        unsafe { ImGui_SetItemTooltip(c"%s".as_ptr(), text.as_ptr()) }
+    }
+    /// Shows a simple text tooltip unconditionally.
+    ///
+    /// For more complex tooltips use `with_tooltip`.
+    pub fn set_tooltip(&self, text: impl IntoCStr) {
+        let text = text.into();
+        unsafe { ImGui_SetTooltip(c"%s".as_ptr(), text.as_ptr()) }
+    }
     pub fn text_link(&self, label: LblId<impl IntoCStr>) -> bool {
         let label = label.into();
         unsafe { ImGui_TextLink(label.as_ptr()) }
@@ -2580,6 +3497,19 @@ impl<A> Ui<A> {
     pub fn is_item_hovered_ex(&self, flags: HoveredFlags) -> bool {
         unsafe { ImGui_IsItemHovered(flags.bits()) }
     }
+    /// Returns `true` if the previous item has been hovered for at least `duration`.
+    ///
+    /// For the standard delays use `is_item_hovered_ex` with `HoveredFlags::DelayShort` or
+    /// `HoveredFlags::DelayNormal` instead, that use the values in `io.HoverDelayShort` and
+    /// `io.HoverDelayNormal`.
+    pub fn is_item_hovered_for(&self, duration: std::time::Duration) -> bool {
+        unsafe {
+            // A delayed query is what keeps the ImGui hover timer running for this item
+            ImGui_IsItemHovered((HoveredFlags::DelayShort | HoveredFlags::NoSharedDelay).bits());
+            ImGui_IsItemHovered(HoveredFlags::None.bits())
+                && (*ImGui_GetCurrentContext()).HoverItemDelayTimer >= duration.as_secs_f32()
+        }
+    }
     pub fn is_item_active(&self) -> bool {
         unsafe { ImGui_IsItemActive() }
     }
@@ -2647,9 +3577,67 @@ impl<A> Ui<A> {
             }
         }
     }
+    /// Computes the size of an item as the built-in widgets do.
+    ///
+    /// For each axis, if `requested` is positive it is used as is; if it is zero, `default` is used;
+    /// if it is negative, it is relative to the right/bottom edge of the available space, so
+    /// `-f32::MIN_POSITIVE` fills all the available space.
+    pub fn calc_item_size(&self, requested: Vector2, default: Vector2) -> Vector2 {
+        unsafe {
+            im_to_v2(ImGui_CalcItemSize(
+                v2_to_im(requested),
+                default.x,
+                default.y,
+            ))
+        }
+    }
     pub fn get_content_region_avail(&self) -> Vector2 {
         unsafe { im_to_v2(ImGui_GetContentRegionAvail()) }
     }
+    /// Gets the maximum position of the content region, in window coordinates.
+    ///
+    /// It is obsolete in Dear ImGui, prefer `get_content_region_avail`, but it is kept for
+    /// porting old code. Inside columns or tables it is that of the current cell.
+    pub fn get_content_region_max(&self) -> Vector2 {
+        unsafe {
+            let ctx = &*ImGui_GetCurrentContext();
+            let window = &*ctx.CurrentWindow;
+            let max = if !window.DC.CurrentColumns.is_null() || !ctx.CurrentTable.is_null() {
+                window.WorkRect.Max
+            } else {
+                window.ContentRegionRect.Max
+            };
+            im_to_v2(max) - im_to_v2(window.Pos)
+        }
+    }
+    /// Gets the minimum position of the content region of the window, in window coordinates.
+    ///
+    /// Like `get_content_region_max`, it is obsolete in Dear ImGui.
+    pub fn get_window_content_region_min(&self) -> Vector2 {
+        unsafe {
+            let window = &*(*ImGui_GetCurrentContext()).CurrentWindow;
+            im_to_v2(window.ContentRegionRect.Min) - im_to_v2(window.Pos)
+        }
+    }
+    /// Gets the maximum position of the content region of the window, in window coordinates.
+    ///
+    /// Like `get_content_region_max`, it is obsolete in Dear ImGui.
+    pub fn get_window_content_region_max(&self) -> Vector2 {
+        unsafe {
+            let window = &*(*ImGui_GetCurrentContext()).CurrentWindow;
+            im_to_v2(window.ContentRegionRect.Max) - im_to_v2(window.Pos)
+        }
+    }
+    /// Gets the space taken by the scrollbars of the current window.
+    ///
+    /// The `x` value is the width of the vertical scrollbar and the `y` value is the height of the
+    /// horizontal one, `0.0` if there is no scrollbar.
+    pub fn get_window_scrollbar_sizes(&self) -> Vector2 {
+        unsafe {
+            let window = &*(*ImGui_GetCurrentContext()).CurrentWindow;
+            im_to_v2(window.ScrollbarSizes)
+        }
+    }
     pub fn get_window_pos(&self) -> Vector2 {
         unsafe { im_to_v2(ImGui_GetWindowPos()) }
     }
@@ -2675,6 +3663,52 @@ impl<A> Ui<A> {
             ImGui_SetScrollY(scroll_y);
         }
     }
+    /// Gets the ID of the current window.
+    ///
+    /// Call it inside a child window to get the ID to be used in [`Ui::sync_scroll`].
+    pub fn get_window_id(&self) -> ImGuiID {
+        unsafe { (*(*ImGui_GetCurrentContext()).CurrentWindow).ID }
+    }
+    /// Synchronizes the vertical scroll of several windows, usually child windows side by side.
+    ///
+    /// The scroll is read from the window the user is interacting with, by dragging its
+    /// scrollbar or by hovering it. If there is none, the first one is used. Then it is copied to
+    /// the other windows, in the next frame.
+    ///
+    /// Call it after the windows have been built, with the IDs from [`Ui::get_window_id`].
+    /// Unknown IDs are ignored.
+    pub fn sync_scroll(&self, ids: &[ImGuiID]) {
+        unsafe {
+            let ctx = &*ImGui_GetCurrentContext();
+            let windows: Vec<*mut ImGuiWindow> = ids
+                .iter()
+                .map(|&id| ImGui_FindWindowByID(id))
+                .filter(|w| !w.is_null())
+                .collect();
+            // Finds the window in `windows` that is `w` or one of its parents
+            let find = |mut w: *mut ImGuiWindow| {
+                while !w.is_null() {
+                    if windows.contains(&w) {
+                        return Some(w);
+                    }
+                    w = (*w).ParentWindow;
+                }
+                None
+            };
+            let Some(source) = find(ctx.ActiveIdWindow)
+                .or_else(|| find(ctx.HoveredWindow))
+                .or_else(|| windows.first().copied())
+            else {
+                return;
+            };
+            let scroll_y = (*source).Scroll.y;
+            for &w in &windows {
+                if w != source && (*w).Scroll.y != scroll_y {
+                    ImGui_SetScrollY1(w, scroll_y);
+                }
+            }
+        }
+    }
     pub fn get_scroll_max_x(&self) -> f32 {
         unsafe { ImGui_GetScrollMaxX() }
     }
@@ -2701,6 +3735,32 @@ impl<A> Ui<A> {
             ImGui_SetScrollFromPosY(local_y, center_y_ratio);
         }
     }
+    /// Scrolls the current window so that the last item is fully visible.
+    ///
+    /// If the item is already visible it does nothing, if not it is placed at the nearest edge.
+    /// It is useful to "jump to the selection" in a list.
+    pub fn scroll_to_item(&self) {
+        let min = self.get_item_rect_min();
+        let max = self.get_item_rect_max();
+        let (pos, visible_min, visible_max) = unsafe {
+            let window = &*(*ImGui_GetCurrentContext()).CurrentWindow;
+            (
+                im_to_v2(window.Pos),
+                im_to_v2(window.InnerRect.Min),
+                im_to_v2(window.InnerRect.Max),
+            )
+        };
+        if min.y < visible_min.y {
+            self.set_scroll_from_pos_y(min.y - pos.y, 0.0);
+        } else if max.y > visible_max.y {
+            self.set_scroll_from_pos_y(max.y - pos.y, 1.0);
+        }
+        if min.x < visible_min.x {
+            self.set_scroll_from_pos_x(min.x - pos.x, 0.0);
+        } else if max.x > visible_max.x {
+            self.set_scroll_from_pos_x(max.x - pos.x, 1.0);
+        }
+    }
     pub fn set_window_pos(&self, pos: Vector2, cond: Cond) {
         unsafe {
             ImGui_SetWindowPos(&v2_to_im(pos), cond.bits());
@@ -2814,6 +3874,22 @@ impl<A> Ui<A> {
     pub fn calc_text_size(&self, text: &str) -> Vector2 {
         self.calc_text_size_ex(text, false, -1.0)
     }
+    /// Like `calc_text_size` but with every tab expanded to the next tab stop, every `tab_width` characters.
+    ///
+    /// Use it to measure plain text that is aligned with tabs, such as logs or source code. Note that
+    /// the text widgets do not expand tabs, so you have to do it yourself before drawing the text,
+    /// for example with [`expand_tabs`].
+    pub fn calc_text_size_with_tabs(&self, text: &str, tab_width: u32) -> Vector2 {
+        self.calc_text_size(&expand_tabs(text, tab_width))
+    }
+    /// Computes the number of lines that `text` takes when wrapped at `wrap_width`.
+    ///
+    /// It counts both the explicit newlines and the wrapped lines. An empty text is one line.
+    pub fn calc_wrapped_line_count(&self, text: &str, wrap_width: f32) -> usize {
+        let size = self.calc_text_size_ex(text, false, wrap_width);
+        let lines = (size.y / self.get_font_size()).round() as usize;
+        lines.max(1)
+    }
     pub fn calc_text_size_ex(
         &self,
         text: &str,
@@ -2830,6 +3906,19 @@ impl<A> Ui<A> {
             ))
         }
     }
+    /// Computes the largest font size, up to `base_size`, that fits `text` in `max_width`.
+    ///
+    /// It assumes that the text width scales linearly with the font size, that is close enough
+    /// for most fonts.
+    pub fn fit_font_size(&self, text: &str, max_width: f32, base_size: f32) -> f32 {
+        let font_size = unsafe { ImGui_GetFontSize() };
+        let width = self.calc_text_size(text).x * base_size / font_size;
+        if width <= max_width {
+            base_size
+        } else {
+            base_size * max_width / width
+        }
+    }
     pub fn set_color_edit_options(&self, flags: ColorEditFlags) {
         unsafe {
             ImGui_SetColorEditOptions(flags.bits());
@@ -2915,17 +4004,22 @@ impl<A> Ui<A> {
         unsafe { im_to_v2(ImGui_GetMousePosOnOpeningCurrentPopup()) }
     }
     pub fn is_mouse_dragging(&self, button: MouseButton) -> bool {
-        unsafe {
-            ImGui_IsMouseDragging(button.bits(), /*lock_threshold*/ -1.0)
-        }
+        self.is_mouse_dragging_ex(button, -1.0)
+    }
+    /// Like `is_mouse_dragging` but the drag starts after the mouse moves `lock_threshold` pixels.
+    ///
+    /// A negative value uses `io.MouseDragThreshold`.
+    pub fn is_mouse_dragging_ex(&self, button: MouseButton, lock_threshold: f32) -> bool {
+        unsafe { ImGui_IsMouseDragging(button.bits(), lock_threshold) }
     }
     pub fn get_mouse_drag_delta(&self, button: MouseButton) -> Vector2 {
-        unsafe {
-            im_to_v2(ImGui_GetMouseDragDelta(
-                button.bits(),
-                /*lock_threshold*/ -1.0,
-            ))
-        }
+        self.get_mouse_drag_delta_ex(button, -1.0)
+    }
+    /// Like `get_mouse_drag_delta` but the delta is zero until the mouse moves `lock_threshold` pixels.
+    ///
+    /// A negative value uses `io.MouseDragThreshold`.
+    pub fn get_mouse_drag_delta_ex(&self, button: MouseButton, lock_threshold: f32) -> Vector2 {
+        unsafe { im_to_v2(ImGui_GetMouseDragDelta(button.bits(), lock_threshold)) }
     }
     pub fn reset_mouse_drag_delta(&self, button: MouseButton) {
         unsafe {
@@ -2997,6 +4091,24 @@ impl<A> Ui<A> {
     pub fn io(&self) -> &ImGuiIO {
         unsafe { &*ImGui_GetIO() }
     }
+    /// Gets the estimated number of frames per second, averaged over the last 60 frames.
+    pub fn framerate(&self) -> f32 {
+        self.io().Framerate
+    }
+    /// Gets the time elapsed since the previous frame.
+    pub fn delta_time(&self) -> std::time::Duration {
+        std::time::Duration::from_secs_f32(self.io().DeltaTime)
+    }
+    /// Gets the vertical mouse wheel movement of this frame, positive is up.
+    ///
+    /// It is the raw value, even if there is no window to scroll.
+    pub fn mouse_wheel(&self) -> f32 {
+        self.io().MouseWheel
+    }
+    /// Gets the horizontal mouse wheel movement of this frame, positive is left.
+    pub fn mouse_wheel_h(&self) -> f32 {
+        self.io().MouseWheelH
+    }
     pub fn font_atlas(&self) -> FontAtlas<'_> {
         unsafe {
             let io = &*ImGui_GetIO();
@@ -3050,6 +4162,104 @@ impl<A> Ui<A> {
     ) -> Option<R> {
         self.with_always_drag_drop_target(move |r| r.map(f))
     }
+    /// Like `with_always_drag_drop_target` but the target is the whole current window, instead
+    /// of the last item.
+    ///
+    /// Items inside the window that are drop targets take precedence, because they are smaller.
+    pub fn with_always_window_drag_drop_target<R>(
+        &self,
+        f: impl FnOnce(Option<DragDropPayloadGetter<'_>>) -> R,
+    ) -> R {
+        let accepting = unsafe {
+            let window = &*(*ImGui_GetCurrentContext()).CurrentWindow;
+            ImGui_BeginDragDropTargetCustom(&window.InnerRect, window.ID)
+        };
+        if !accepting {
+            return f(None);
+        }
+        let payload = DragDropPayloadGetter {
+            _dummy: PhantomData,
+        };
+        let r = f(Some(payload));
+        unsafe { ImGui_EndDragDropTarget() }
+        r
+    }
+    /// Like `with_drag_drop_target` but the target is the whole current window.
+    ///
+    /// `f` is only called while a drag&drop operation is over the window.
+    pub fn with_window_drag_drop_target<R>(
+        &self,
+        f: impl FnOnce(DragDropPayloadGetter<'_>) -> R,
+    ) -> Option<R> {
+        self.with_always_window_drag_drop_target(move |r| r.map(f))
+    }
+    /// Gets the payload of the drag&drop operation in progress, if any, without accepting it.
+    ///
+    /// It can be called from anywhere, not only from a drop target.
+    pub fn current_drag_drop_payload(&self) -> Option<DragDropPayload<'_>> {
+        unsafe {
+            let pay = ImGui_GetDragDropPayload();
+            if pay.is_null() {
+                None
+            } else {
+                Some(DragDropPayload { pay: &*pay })
+            }
+        }
+    }
+    /// Gets the type of the drag&drop operation in progress, if any.
+    ///
+    /// Useful to highlight the drop targets that would accept it.
+    pub fn current_drag_drop_type(&self) -> Option<String> {
+        self.current_drag_drop_payload()
+            .map(|pay| pay.type_().into_owned())
+    }
+
+    /// Calls `f` with the legacy columns layout, see `Columns`.
+    ///
+    /// Tables are more powerful, but columns are simpler for quick layouts. Use `next_column` to
+    /// move to the next column.
+    pub fn with_columns<R>(
+        &self,
+        count: i32,
+        id: impl IntoCStr,
+        border: bool,
+        f: impl FnOnce() -> R,
+    ) -> R {
+        let id = id.into();
+        unsafe {
+            ImGui_Columns(count, id.as_ptr(), border);
+        }
+        let r = f();
+        unsafe {
+            ImGui_Columns(1, null(), false);
+        }
+        r
+    }
+    pub fn next_column(&self) {
+        unsafe { ImGui_NextColumn() }
+    }
+    pub fn get_column_index(&self) -> i32 {
+        unsafe { ImGui_GetColumnIndex() }
+    }
+    /// Gets the width of a column, `None` means the current one.
+    pub fn get_column_width(&self, column_index: Option<i32>) -> f32 {
+        unsafe { ImGui_GetColumnWidth(column_index.unwrap_or(-1)) }
+    }
+    /// Sets the width of a column, `None` means the current one.
+    pub fn set_column_width(&self, column_index: Option<i32>, width: f32) {
+        unsafe { ImGui_SetColumnWidth(column_index.unwrap_or(-1), width) }
+    }
+    /// Gets the position of the left side of a column, `None` means the current one.
+    pub fn get_column_offset(&self, column_index: Option<i32>) -> f32 {
+        unsafe { ImGui_GetColumnOffset(column_index.unwrap_or(-1)) }
+    }
+    /// Sets the position of the left side of a column, `None` means the current one.
+    pub fn set_column_offset(&self, column_index: Option<i32>, offset_x: f32) {
+        unsafe { ImGui_SetColumnOffset(column_index.unwrap_or(-1), offset_x) }
+    }
+    pub fn get_columns_count(&self) -> i32 {
+        unsafe { ImGui_GetColumnsCount() }
+    }
 
     #[must_use]
     pub fn list_clipper(&self, items_count: usize) -> ListClipper {
@@ -3108,6 +4318,43 @@ impl<A> Ui<A> {
             &*font
         }
     }
+    /// Gets the font details of the currently pushed font, as used by the text widgets.
+    ///
+    /// Fonts are baked at a single size, so the glyph metrics are in `ImFont::FontSize` units.
+    /// Scale them by `get_font_size() / font.FontSize` to get the actual size of the drawn text.
+    pub fn current_font(&self) -> &ImFont {
+        unsafe { &*ImGui_GetFont() }
+    }
+}
+
+/// Replaces every tab in `text` with spaces up to the next tab stop, every `tab_width` characters.
+///
+/// The tab stops start again after every newline. If there are no tabs, the text is borrowed.
+pub fn expand_tabs(text: &str, tab_width: u32) -> Cow<'_, str> {
+    if !text.contains('\t') {
+        return Cow::Borrowed(text);
+    }
+    let tab_width = tab_width.max(1) as usize;
+    let mut expanded = String::with_capacity(text.len());
+    let mut column = 0;
+    for c in text.chars() {
+        match c {
+            '\t' => {
+                let n = tab_width - column % tab_width;
+                expanded.extend(std::iter::repeat_n(' ', n));
+                column += n;
+            }
+            '\n' => {
+                expanded.push(c);
+                column = 0;
+            }
+            _ => {
+                expanded.push(c);
+                column += 1;
+            }
+        }
+    }
+    Cow::Owned(expanded)
 }
 
 pub struct ListClipper {
@@ -3124,17 +4371,207 @@ impl ListClipper {
     }
 
     pub fn with(self, mut f: impl FnMut(usize)) {
+        for r in self.begin() {
+            for i in r {
+                f(i);
+            }
+        }
+    }
+
+    /// Starts the clipper, for manual stepping instead of using a closure.
+    ///
+    /// Each step returns the range of items to draw. It is also an `Iterator`.
+    pub fn begin(self) -> ListClipperStepper {
         unsafe {
-            let mut clip = ImGuiListClipper::new();
+            // Boxed because ImGui keeps a pointer to the clipper
+            let mut clip = Box::new(ImGuiListClipper::new());
             clip.Begin(self.items_count as i32, self.items_height);
             for r in self.included_ranges {
                 clip.IncludeItemsByIndex(r.start as i32, r.end as i32);
             }
-            while clip.Step() {
-                for i in clip.DisplayStart..clip.DisplayEnd {
-                    f(i as usize);
+            ListClipperStepper { clip, done: false }
+        }
+    }
+
+    /// Like `with` but for items of different heights.
+    ///
+    /// `heights` has the height of each item, including the item spacing, and must have at least
+    /// `items_count` values. The `items_height` value is not used.
+    ///
+    /// The skipped items are replaced by dummy space, so this should be used directly inside a
+    /// window, not in a table.
+    pub fn with_heights(self, heights: &[f32], mut f: impl FnMut(usize)) {
+        let heights = &heights[..self.items_count];
+        let (clip_min, clip_max, spacing_y) = unsafe {
+            let ctx = &*ImGui_GetCurrentContext();
+            let window = &*ctx.CurrentWindow;
+            (
+                window.ClipRect.Min.y - window.DC.CursorPos.y,
+                window.ClipRect.Max.y - window.DC.CursorPos.y,
+                ctx.Style.ItemSpacing.y,
+            )
+        };
+
+        // The visible items plus the included ranges, sorted and merged
+        let mut y = 0.0;
+        let mut visible = heights.len()..heights.len();
+        for (i, h) in heights.iter().enumerate() {
+            if visible.start == heights.len() && y + h > clip_min {
+                visible.start = i;
+            }
+            if y >= clip_max {
+                visible.end = i;
+                break;
+            }
+            y += h;
+        }
+        let mut ranges = self.included_ranges;
+        ranges.push(visible);
+        for r in &mut ranges {
+            r.end = r.end.min(heights.len());
+        }
+        ranges.sort_by_key(|r| r.start);
+
+        let skip = |h: f32| {
+            if h > 0.0 {
+                unsafe {
+                    ImGui_Dummy(&im_vec2(0.0, (h - spacing_y).max(0.0)));
                 }
             }
+        };
+        let mut next = 0;
+        for r in ranges {
+            let start = r.start.max(next);
+            if start >= r.end {
+                continue;
+            }
+            skip(heights[next..start].iter().sum());
+            for i in start..r.end {
+                f(i);
+            }
+            next = r.end;
+        }
+        skip(heights[next..].iter().sum());
+    }
+}
+
+/// A running [`ListClipper`], see [`ListClipper::begin`].
+///
+/// If dropped before the last step, it ends the clipper.
+pub struct ListClipperStepper {
+    clip: Box<ImGuiListClipper>,
+    done: bool,
+}
+
+impl ListClipperStepper {
+    /// Advances the clipper, returns the range of items to draw or `None` when finished.
+    pub fn step(&mut self) -> Option<std::ops::Range<usize>> {
+        if self.done {
+            return None;
+        }
+        unsafe {
+            if self.clip.Step() {
+                Some(self.clip.DisplayStart as usize..self.clip.DisplayEnd as usize)
+            } else {
+                self.done = true;
+                None
+            }
+        }
+    }
+}
+
+impl Iterator for ListClipperStepper {
+    type Item = std::ops::Range<usize>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.step()
+    }
+}
+
+impl Drop for ListClipperStepper {
+    fn drop(&mut self) {
+        if !self.done {
+            unsafe {
+                self.clip.End();
+            }
+        }
+    }
+}
+
+/// A text filter for a search box, with the ImGui mini-syntax.
+///
+/// Filters are separated by commas, and a filter starting with `-` excludes the matching texts,
+/// for example `"aaa,bbb,-ccc"`. It keeps its own text buffer, so it has to be stored
+/// between frames.
+pub struct TextFilter {
+    // Boxed because the parsed filters point into the inner buffer
+    inner: Box<ImGuiTextFilter>,
+}
+
+impl Default for TextFilter {
+    fn default() -> Self {
+        TextFilter::new()
+    }
+}
+
+impl Drop for TextFilter {
+    fn drop(&mut self) {
+        unsafe {
+            ImGui_MemFree(self.inner.Filters.Data as *mut c_void);
+        }
+    }
+}
+
+impl TextFilter {
+    pub fn new() -> TextFilter {
+        unsafe {
+            TextFilter {
+                inner: Box::new(ImGuiTextFilter::new(c"".as_ptr())),
+            }
+        }
+    }
+    /// Replaces the filter text, it is truncated if it doesn't fit in the buffer.
+    pub fn set_filter(&mut self, filter: &str) {
+        let buf = &mut self.inner.InputBuf;
+        let mut len = filter.len().min(buf.len() - 1);
+        while !filter.is_char_boundary(len) {
+            len -= 1;
+        }
+        for (d, s) in buf.iter_mut().zip(&filter.as_bytes()[..len]) {
+            *d = *s as c_char;
+        }
+        buf[len] = 0;
+        unsafe {
+            self.inner.Build();
+        }
+    }
+    /// Gets the current filter text.
+    pub fn filter(&self) -> String {
+        unsafe {
+            CStr::from_ptr(self.inner.InputBuf.as_ptr())
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+    /// Empties the filter, so that everything passes.
+    pub fn clear(&mut self) {
+        self.set_filter("");
+    }
+    /// Checks if there is any filter, that is, if not everything passes.
+    pub fn is_active(&self) -> bool {
+        self.inner.Filters.Size > 0
+    }
+    /// Draws the input box to edit the filter, returns `true` if it changed.
+    ///
+    /// The width is that of `set_next_item_width`, if any, or the default item width.
+    pub fn draw<A, S: IntoCStr>(&mut self, _ui: &Ui<A>, label: LblId<S>) -> bool {
+        let label = label.into();
+        unsafe { self.inner.Draw(label.as_ptr(), 0.0) }
+    }
+    /// Checks if a text passes the filter.
+    pub fn pass_filter(&self, text: &str) -> bool {
+        unsafe {
+            let (start, end) = text_ptrs(text);
+            self.inner.PassFilter(start, end)
         }
     }
 }
@@ -3193,6 +4630,16 @@ impl<A> Ui<A> {
     ) -> ImGuiID {
         unsafe { ImGui_DockSpace(id, &v2_to_im(size), flags.bits(), std::ptr::null()) }
     }
+    /// Like `dock_space` but only windows with a compatible class can be docked into it.
+    pub fn dock_space_with_class(
+        &self,
+        id: ImGuiID,
+        size: Vector2,
+        flags: DockNodeFlags,
+        window_class: &WindowClass,
+    ) -> ImGuiID {
+        unsafe { ImGui_DockSpace(id, &v2_to_im(size), flags.bits(), &window_class.0) }
+    }
     pub fn dock_space_over_viewport(
         &self,
         dockspace_id: ImGuiID,
@@ -3212,13 +4659,176 @@ impl<A> Ui<A> {
             ImGui_SetNextWindowDockID(dock_id, cond.bits());
         }
     }
-    //SetNextWindowClass(const ImGuiWindowClass* window_class)
+    /// Like `foreground_draw_list` but for a specific viewport.
+    pub fn viewport_foreground_draw_list(&self, viewport: &Viewport<'_>) -> WindowDrawList<'_, A> {
+        unsafe {
+            let ptr = ImGui_GetForegroundDrawList(viewport.ptr as *const _ as *mut _);
+            WindowDrawList { ui: self, ptr }
+        }
+    }
+    /// Like `background_draw_list` but for a specific viewport.
+    pub fn viewport_background_draw_list(&self, viewport: &Viewport<'_>) -> WindowDrawList<'_, A> {
+        unsafe {
+            let ptr = ImGui_GetBackgroundDrawList(viewport.ptr as *const _ as *mut _);
+            WindowDrawList { ui: self, ptr }
+        }
+    }
+    /// Sets the class of the next window, that controls which windows can be docked together.
+    pub fn set_next_window_class(&self, window_class: &WindowClass) {
+        unsafe {
+            ImGui_SetNextWindowClass(&window_class.0);
+        }
+    }
     pub fn get_window_doc_id(&self) -> ImGuiID {
         unsafe { ImGui_GetWindowDockID() }
     }
     pub fn is_window_docked(&self) -> bool {
         unsafe { ImGui_IsWindowDocked() }
     }
+    /// Gets a dock node, to inspect the current docking layout.
+    ///
+    /// The `id` is that of a dock space or of any of its nodes, such as `get_window_doc_id`.
+    pub fn dock_node(&self, id: ImGuiID) -> Option<DockNode<'_>> {
+        unsafe {
+            ImGui_DockBuilderGetNode(id)
+                .as_ref()
+                .map(|ptr| DockNode { ptr })
+        }
+    }
+}
+
+/// A node of the docking layout, see [`Ui::dock_node`].
+///
+/// A node is either split in two child nodes or it is a leaf that holds docked windows.
+#[cfg(feature = "docking")]
+pub struct DockNode<'a> {
+    ptr: &'a ImGuiDockNode,
+}
+
+#[cfg(feature = "docking")]
+impl<'a> DockNode<'a> {
+    pub fn id(&self) -> ImGuiID {
+        self.ptr.ID
+    }
+    pub fn flags(&self) -> DockNodeFlags {
+        DockNodeFlags::from_bits_truncate(self.ptr.MergedFlags)
+    }
+    pub fn pos(&self) -> Vector2 {
+        im_to_v2(self.ptr.Pos)
+    }
+    pub fn size(&self) -> Vector2 {
+        im_to_v2(self.ptr.Size)
+    }
+    /// The parent node, `None` for the root node of a dock space.
+    pub fn parent(&self) -> Option<DockNode<'a>> {
+        unsafe { self.ptr.ParentNode.as_ref().map(|ptr| DockNode { ptr }) }
+    }
+    pub fn is_split(&self) -> bool {
+        !self.ptr.ChildNodes[0].is_null()
+    }
+    /// The two child nodes, if it is split: left and right, or top and bottom.
+    pub fn children(&self) -> Option<[DockNode<'a>; 2]> {
+        unsafe {
+            let [a, b] = self.ptr.ChildNodes;
+            Some([DockNode { ptr: a.as_ref()? }, DockNode { ptr: b.as_ref()? }])
+        }
+    }
+    /// The axis along which this node is split, `Axis::X` means side by side.
+    pub fn split_axis(&self) -> Option<Axis> {
+        if !self.is_split() {
+            return None;
+        }
+        Axis::from_bits(self.ptr.SplitAxis)
+    }
+    /// The size of the first child node relative to this one, along the split axis.
+    pub fn split_ratio(&self) -> Option<f32> {
+        let [first, _] = self.children()?;
+        let ratio = match self.split_axis()? {
+            Axis::X => first.size().x / self.size().x,
+            Axis::Y => first.size().y / self.size().y,
+        };
+        Some(ratio)
+    }
+    /// Whether this is the central node of a dock space, the one that is kept even when empty.
+    pub fn is_central_node(&self) -> bool {
+        std::ptr::eq(self.ptr.CentralNode, self.ptr)
+    }
+    /// The names of the windows docked in this node, in tab order.
+    pub fn window_names(&self) -> Vec<String> {
+        let windows = &self.ptr.Windows;
+        (0..windows.Size as usize)
+            .map(|i| unsafe {
+                let window = &**windows.Data.add(i);
+                CStr::from_ptr(window.Name).to_string_lossy().into_owned()
+            })
+            .collect()
+    }
+    /// The ID of the selected tab, that is of the visible window.
+    pub fn selected_tab_id(&self) -> ImGuiID {
+        self.ptr.SelectedTabId
+    }
+}
+
+/// The docking class of a window, see [`Ui::set_next_window_class`].
+///
+/// By default windows with a class can only be docked with windows of the same class,
+/// and windows without a class (`class_id == 0`) can be docked anywhere.
+#[cfg(feature = "docking")]
+#[derive(Clone)]
+pub struct WindowClass(ImGuiWindowClass);
+
+#[cfg(feature = "docking")]
+impl WindowClass {
+    /// Creates a new window class with the given ID, use `0` for no class.
+    pub fn new(class_id: ImGuiID) -> WindowClass {
+        // SAFETY: it is a POD, and all zeros is the default, except for the fields below
+        let mut wc: ImGuiWindowClass = unsafe { std::mem::zeroed() };
+        wc.ClassId = class_id;
+        wc.ParentViewportId = ImGuiID::MAX;
+        wc.DockingAllowUnclassed = true;
+        WindowClass(wc)
+    }
+    /// The ID of the class.
+    pub fn class_id(&self) -> ImGuiID {
+        self.0.ClassId
+    }
+    /// Hint for the platform backend, `None` means that the parent is the main viewport.
+    pub fn parent_viewport_id(mut self, id: Option<ImGuiID>) -> Self {
+        self.0.ParentViewportId = id.unwrap_or(ImGuiID::MAX);
+        self
+    }
+    /// Viewport flags to set when the window has its own viewport.
+    pub fn viewport_flags_override_set(mut self, flags: ViewportFlags) -> Self {
+        self.0.ViewportFlagsOverrideSet = flags.bits();
+        self
+    }
+    /// Viewport flags to clear when the window has its own viewport.
+    pub fn viewport_flags_override_clear(mut self, flags: ViewportFlags) -> Self {
+        self.0.ViewportFlagsOverrideClear = flags.bits();
+        self
+    }
+    /// Tab item flags to set when the window is docked.
+    pub fn tab_item_flags_override_set(mut self, flags: TabItemFlags) -> Self {
+        self.0.TabItemFlagsOverrideSet = flags.bits();
+        self
+    }
+    /// Dock node flags to set when the window is docked.
+    pub fn dock_node_flags_override_set(mut self, flags: DockNodeFlags) -> Self {
+        self.0.DockNodeFlagsOverrideSet = flags.bits();
+        self
+    }
+    /// Always show a tab bar, even with only one window docked.
+    pub fn docking_always_tab_bar(mut self, always: bool) -> Self {
+        self.0.DockingAlwaysTabBar = always;
+        self
+    }
+    /// Windows without a class can be docked with windows of this class.
+    ///
+    /// By default it is `true`.
+    pub fn docking_allow_unclassed(mut self, allow: bool) -> Self {
+        self.0.DockingAllowUnclassed = allow;
+        self
+    }
 }
 
 /// Identifier of a registered font. Only the values obtained from the latest call to [`UiBuilder::build_custom_atlas`] are actually valid.
@@ -3481,12 +5091,44 @@ unsafe extern "C" fn call_size_callback<A>(ptr: *mut ImGuiSizeCallbackData) {
     Ui::<A>::run_callback(id, data);
 }
 
+/// The number of quads reserved at once by the batch functions of `WindowDrawList`.
+///
+/// Small enough to fit in the 16-bit indices of a draw command.
+const PRIM_BATCH_SIZE: usize = 4096;
+
 pub struct WindowDrawList<'ui, A> {
     ui: &'ui Ui<A>,
     ptr: *mut ImDrawList,
 }
 
 impl<A> WindowDrawList<'_, A> {
+    /// The number of vertices in this draw list so far.
+    pub fn vtx_count(&self) -> usize {
+        unsafe { (*self.ptr).VtxBuffer.Size as usize }
+    }
+    /// The number of indices in this draw list so far.
+    pub fn idx_count(&self) -> usize {
+        unsafe { (*self.ptr).IdxBuffer.Size as usize }
+    }
+    /// Gets the current clip rectangle of this draw list, as `(min, max)` in screen coordinates.
+    pub fn clip_rect(&self) -> (Vector2, Vector2) {
+        unsafe {
+            let stack = &(*self.ptr)._ClipRectStack;
+            let cr = if stack.Size > 0 {
+                *stack.Data.add(stack.Size as usize - 1)
+            } else {
+                (*self.ptr)._CmdHeader.ClipRect
+            };
+            (Vector2::new(cr.x, cr.y), Vector2::new(cr.z, cr.w))
+        }
+    }
+    /// Checks if a rectangle is fully outside the current clip rectangle.
+    ///
+    /// Use it to skip drawing things that would not be visible anyway.
+    pub fn is_rect_clipped(&self, p_min: Vector2, p_max: Vector2) -> bool {
+        let (c_min, c_max) = self.clip_rect();
+        p_max.x <= c_min.x || p_max.y <= c_min.y || p_min.x >= c_max.x || p_min.y >= c_max.y
+    }
     pub fn add_line(&self, p1: Vector2, p2: Vector2, color: Color, thickness: f32) {
         unsafe {
             ImDrawList_AddLine(
@@ -3498,6 +5140,58 @@ impl<A> WindowDrawList<'_, A> {
             );
         }
     }
+    /// Draws many lines of the same color and thickness, faster than calling `add_line` for each.
+    ///
+    /// The vertices are reserved in batches and each line is a simple quad, so they are not
+    /// anti-aliased.
+    pub fn add_lines(&self, lines: &[(Vector2, Vector2)], color: Color, thickness: f32) {
+        let color = color.as_u32();
+        let half = thickness / 2.0;
+        unsafe {
+            let uv = (*(*self.ptr)._Data).TexUvWhitePixel;
+            for chunk in lines.chunks(PRIM_BATCH_SIZE) {
+                let n = chunk.len() as i32;
+                ImDrawList_PrimReserve(self.ptr, 6 * n, 4 * n);
+                for &(p1, p2) in chunk {
+                    let d = p2 - p1;
+                    let len = (d.x * d.x + d.y * d.y).sqrt();
+                    let normal = if len > 0.0 {
+                        Vector2::new(-d.y, d.x) * (half / len)
+                    } else {
+                        Vector2::new(0.0, 0.0)
+                    };
+                    ImDrawList_PrimQuadUV(
+                        self.ptr,
+                        &v2_to_im(p1 + normal),
+                        &v2_to_im(p2 + normal),
+                        &v2_to_im(p2 - normal),
+                        &v2_to_im(p1 - normal),
+                        &uv,
+                        &uv,
+                        &uv,
+                        &uv,
+                        color,
+                    );
+                }
+            }
+        }
+    }
+    /// Draws many filled rectangles of the same color, as `(p_min, p_max)`, faster than calling
+    /// `add_rect_filled` for each.
+    ///
+    /// The vertices are reserved in batches, and the rectangles have no rounding.
+    pub fn add_rects_filled(&self, rects: &[(Vector2, Vector2)], color: Color) {
+        let color = color.as_u32();
+        unsafe {
+            for chunk in rects.chunks(PRIM_BATCH_SIZE) {
+                let n = chunk.len() as i32;
+                ImDrawList_PrimReserve(self.ptr, 6 * n, 4 * n);
+                for &(p_min, p_max) in chunk {
+                    ImDrawList_PrimRect(self.ptr, &v2_to_im(p_min), &v2_to_im(p_max), color);
+                }
+            }
+        }
+    }
     pub fn add_rect(
         &self,
         p_min: Vector2,
@@ -3559,6 +5253,26 @@ impl<A> WindowDrawList<'_, A> {
             );
         }
     }
+    /// Draws a filled rectangle with a vertical gradient, from `top` to `bottom`.
+    pub fn add_rect_filled_vertical_gradient(
+        &self,
+        p_min: Vector2,
+        p_max: Vector2,
+        top: Color,
+        bottom: Color,
+    ) {
+        self.add_rect_filled_multicolor(p_min, p_max, top, top, bottom, bottom);
+    }
+    /// Draws a filled rectangle with a horizontal gradient, from `left` to `right`.
+    pub fn add_rect_filled_horizontal_gradient(
+        &self,
+        p_min: Vector2,
+        p_max: Vector2,
+        left: Color,
+        right: Color,
+    ) {
+        self.add_rect_filled_multicolor(p_min, p_max, left, right, right, left);
+    }
     pub fn add_quad(
         &self,
         p1: Vector2,
@@ -3735,6 +5449,19 @@ impl<A> WindowDrawList<'_, A> {
             ImDrawList_AddText(self.ptr, &v2_to_im(pos), color.as_u32(), start, end);
         }
     }
+    /// Draws a text with a drop shadow, to make it readable over any background.
+    ///
+    /// The shadow is the same text drawn first 1 pixel down and right, in `shadow_color`.
+    pub fn add_text_with_shadow(
+        &self,
+        pos: Vector2,
+        color: Color,
+        shadow_color: Color,
+        text: &str,
+    ) {
+        self.add_text(pos + Vector2::new(1.0, 1.0), shadow_color, text);
+        self.add_text(pos, color, text);
+    }
     pub fn add_text_ex(
         &self,
         font: FontId,
@@ -3860,6 +5587,30 @@ impl<A> WindowDrawList<'_, A> {
             );
         }
     }
+    /// Draws a custom rectangle of the font atlas, with its upper-left corner at `p_min`.
+    ///
+    /// The size is that of the rectangle multiplied by `scale`. Returns the lower-right corner.
+    pub fn add_custom_rect(
+        &self,
+        ridx: CustomRectIndex,
+        p_min: Vector2,
+        scale: f32,
+        color: Color,
+    ) -> Vector2 {
+        let atlas = self.ui.font_atlas();
+        let rect = atlas.get_custom_rect(ridx);
+        let tex_size = atlas.texture_size();
+        let inv_tex_w = 1.0 / tex_size[0] as f32;
+        let inv_tex_h = 1.0 / tex_size[1] as f32;
+        let uv0 = vec2(rect.X as f32 * inv_tex_w, rect.Y as f32 * inv_tex_h);
+        let uv1 = vec2(
+            (rect.X + rect.Width) as f32 * inv_tex_w,
+            (rect.Y + rect.Height) as f32 * inv_tex_h,
+        );
+        let p_max = p_min + scale * vec2(rect.Width as f32, rect.Height as f32);
+        self.add_image(atlas.texture_id(), p_min, p_max, uv0, uv1, color);
+        p_max
+    }
     pub fn add_image_quad(
         &self,
         user_texture_id: TextureId,
@@ -4093,6 +5844,46 @@ impl Pushable for FontId {
     }
 }
 
+/// A [`Pushable`] that pushes a font scaled to a given size in pixels.
+///
+/// Fonts are baked at a single size, so the glyphs are scaled and may look blurry if the size
+/// is much bigger than the size of the font. Consider adding more sizes to the font atlas.
+///
+/// The scale is a property of the font itself, so while pushed it also applies to any window
+/// begun inside, such as tooltips, popups or child windows, if they use the same font.
+#[derive(Debug, Clone)]
+pub struct FontSize {
+    font: FontId,
+    size: f32,
+    prev_scale: Cell<f32>,
+}
+
+impl FontSize {
+    pub fn new(font: FontId, size: f32) -> Self {
+        FontSize {
+            font,
+            size,
+            prev_scale: Cell::new(1.0),
+        }
+    }
+}
+
+impl Pushable for FontSize {
+    unsafe fn push(&self) {
+        let font = font_ptr(self.font);
+        self.prev_scale.set((*font).Scale);
+        if (*font).FontSize > 0.0 {
+            (*font).Scale = self.size / (*font).FontSize;
+        }
+        ImGui_PushFont(font);
+    }
+    unsafe fn pop(&self) {
+        // Restore the scale first, in case the previous font is the same one
+        (*font_ptr(self.font)).Scale = self.prev_scale.get();
+        ImGui_PopFont();
+    }
+}
+
 pub type StyleColor = (ColorId, Color);
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -4183,7 +5974,7 @@ impl Pushable for Style {
             StyleValue::F32(f) => ImGui_PushStyleVar(self.0.bits(), f),
             StyleValue::Vec2(v) => ImGui_PushStyleVar1(self.0.bits(), &v2_to_im(v)),
             StyleValue::X(x) => ImGui_PushStyleVarX(self.0.bits(), x),
-            StyleValue::Y(y) => ImGui_PushStyleVarX(self.0.bits(), y),
+            StyleValue::Y(y) => ImGui_PushStyleVarY(self.0.bits(), y),
         }
     }
     unsafe fn pop(&self) {
@@ -4247,6 +6038,37 @@ impl Pushable for TextWrapPos {
     }
 }
 
+/// A [`Pushable`] that sets the mouse cursor, and restores the previous one when popped.
+///
+/// If the cursor is changed again while pushed, for example by a hovered text input, then that
+/// one is kept.
+#[derive(Debug, Clone)]
+pub struct MouseCursorOverride {
+    cursor: MouseCursor,
+    prev: Cell<ImGuiMouseCursor>,
+}
+
+impl MouseCursorOverride {
+    pub fn new(cursor: MouseCursor) -> Self {
+        MouseCursorOverride {
+            cursor,
+            prev: Cell::new(MouseCursor::Arrow.bits()),
+        }
+    }
+}
+
+impl Pushable for MouseCursorOverride {
+    unsafe fn push(&self) {
+        self.prev.set(ImGui_GetMouseCursor());
+        ImGui_SetMouseCursor(self.cursor.bits());
+    }
+    unsafe fn pop(&self) {
+        if ImGui_GetMouseCursor() == self.cursor.bits() {
+            ImGui_SetMouseCursor(self.prev.get());
+        }
+    }
+}
+
 impl Pushable for (ItemFlags, bool) {
     unsafe fn push(&self) {
         ImGui_PushItemFlag(self.0.bits(), self.1);
@@ -4288,20 +6110,82 @@ impl Viewport<'_> {
     pub fn work_size(&self) -> Vector2 {
         im_to_v2(self.ptr.WorkSize)
     }
+    /// The DPI scale of the monitor this viewport is in.
+    #[cfg(feature = "docking")]
+    pub fn dpi_scale(&self) -> f32 {
+        self.ptr.DpiScale
+    }
+    /// The platform window handle, as set by the backend, such as a `HWND` or a `GLFWwindow*`.
+    ///
+    /// May be null if the backend does not set it.
+    pub fn platform_handle(&self) -> *mut c_void {
+        self.ptr.PlatformHandle
+    }
 }
 
-decl_builder_with_opt! { TableConfig, ImGui_BeginTable, ImGui_EndTable () (S: IntoCStr)
+/// Gets a column of the current table, `None` means the current column.
+unsafe fn table_column<'a>(column_n: Option<i32>) -> Option<&'a ImGuiTableColumn> {
+    let table = (*ImGui_GetCurrentContext()).CurrentTable;
+    if table.is_null() {
+        return None;
+    }
+    let table = &*table;
+    let column_n = column_n.unwrap_or(table.CurrentColumn);
+    if column_n < 0 || column_n >= table.ColumnsCount {
+        return None;
+    }
+    Some(&*table.Columns.Data.add(column_n as usize))
+}
+
+unsafe fn table_begin_wrapper(
+    str_id: *const c_char,
+    column: i32,
+    mut flags: ImGuiTableFlags,
+    outer_size: &ImVec2,
+    inner_width: f32,
+    row_stripes: Option<(Color, Color)>,
+) -> bool {
+    // The row colors are read up to `EndTable`, and if the table scrolls that is inside a child
+    // window, so they are pushed outside of the table.
+    if let Some((even, odd)) = row_stripes {
+        ImGui_PushStyleColor1(ColorId::TableRowBg.bits(), &even.into());
+        ImGui_PushStyleColor1(ColorId::TableRowBgAlt.bits(), &odd.into());
+        flags |= TableFlags::RowBg.bits();
+    }
+    let res = ImGui_BeginTable(str_id, column, flags, outer_size, inner_width);
+    if !res && row_stripes.is_some() {
+        ImGui_PopStyleColor(2);
+    }
+    res
+}
+
+unsafe fn table_end_wrapper(row_stripes: bool) {
+    ImGui_EndTable();
+    if row_stripes {
+        ImGui_PopStyleColor(2);
+    }
+}
+
+decl_builder_with_opt! { TableConfig, table_begin_wrapper, table_end_wrapper [row_stripes = row_stripes.is_some()] () (S: IntoCStr)
     (
         str_id (S::Temp) (str_id.as_ptr()),
         column (i32) (column),
         flags (TableFlags) (flags.bits()),
         outer_size (ImVec2) (&outer_size),
         inner_width (f32) (inner_width),
+        row_stripes (Option<(Color, Color)>) (row_stripes),
     )
     {
         decl_builder_setter!{flags: TableFlags}
         decl_builder_setter_vector2!{outer_size: Vector2}
         decl_builder_setter!{inner_width: f32}
+        /// Colors the background of the data rows alternating `even` and `odd`.
+        ///
+        /// It implies `TableFlags::RowBg`. Header rows are not counted.
+        pub fn row_stripes(mut self, even: Color, odd: Color) -> Self {
+            self.row_stripes = Some((even, odd));
+            self
+        }
     }
     {
         pub fn table_config<S: IntoCStr>(&self, str_id: LblId<S>, column: i32) -> TableConfig<S> {
@@ -4311,6 +6195,7 @@ decl_builder_with_opt! { TableConfig, ImGui_BeginTable, ImGui_EndTable () (S: In
                 flags: TableFlags::None,
                 outer_size: im_vec2(0.0, 0.0),
                 inner_width: 0.0,
+                row_stripes: None,
                 push: (),
             }
         }
@@ -4387,6 +6272,27 @@ decl_builder_with_opt! { TableConfig, ImGui_BeginTable, ImGui_EndTable () (S: In
                 CStr::from_ptr(c_str).to_string_lossy().into_owned()
             }
         }
+        /// Gets the current width of a column, in pixels.
+        pub fn table_get_column_width(&self, column_n: Option<i32>) -> f32 {
+            unsafe {
+                table_column(column_n).map(|c| c.WidthGiven).unwrap_or(0.0)
+            }
+        }
+        /// Gets the value that restores the current size of a column, when passed as
+        /// `init_width_or_weight` to `table_setup_column`.
+        ///
+        /// That is the weight for stretch columns and the width for fixed ones.
+        pub fn table_get_column_width_or_weight(&self, column_n: Option<i32>) -> f32 {
+            unsafe {
+                table_column(column_n).map(|c| {
+                    if TableColumnFlags::from_bits_truncate(c.Flags).contains(TableColumnFlags::WidthStretch) {
+                        c.StretchWeight
+                    } else {
+                        c.WidthRequest
+                    }
+                }).unwrap_or(0.0)
+            }
+        }
         pub fn table_set_column_enabled(&self, column_n: Option<i32>, enabled: bool) {
             unsafe {
                 ImGui_TableSetColumnEnabled(column_n.unwrap_or(-1), enabled);
@@ -4454,6 +6360,35 @@ impl DragDropPayloadSetter<'_> {
         };
         unsafe { ImGui_SetDragDropPayload(type_.into().as_ptr(), ptr, len, cond.bits()) }
     }
+    /// Sets the payload and calls `preview` to draw what follows the mouse cursor while dragging.
+    ///
+    /// The drag&drop source is a tooltip, so any widget drawn by `preview` is shown there.
+    pub fn set_with_preview(
+        self,
+        type_: impl IntoCStr,
+        data: &[u8],
+        cond: DragDropPayloadCond,
+        preview: impl FnOnce(),
+    ) -> bool {
+        let accepted = self.set(type_, data, cond);
+        preview();
+        accepted
+    }
+    /// Sets the payload as a serialized value.
+    ///
+    /// It will return `false` if the value could not be serialized.
+    #[cfg(feature = "serde")]
+    pub fn set_typed<T: serde::Serialize>(
+        self,
+        type_: impl IntoCStr,
+        value: &T,
+        cond: DragDropPayloadCond,
+    ) -> bool {
+        match bincode::serialize(value) {
+            Ok(data) => self.set(type_, &data, cond),
+            Err(_) => false,
+        }
+    }
 }
 
 /// Helpar class to get the drag&drop payload.
@@ -4493,6 +6428,18 @@ impl<'a> DragDropPayloadGetter<'a> {
             }
         }
     }
+    /// Gets the payload of the given type, deserialized as a value.
+    ///
+    /// It will return `None` if there is no payload of that type or if it could not be deserialized.
+    #[cfg(feature = "serde")]
+    pub fn by_type_typed<T: serde::de::DeserializeOwned>(
+        &self,
+        type_: impl IntoCStr,
+        flags: DragDropAcceptFlags,
+    ) -> Option<T> {
+        let pay = self.by_type(type_, flags)?;
+        bincode::deserialize(pay.data()).ok()
+    }
     pub fn peek(&self) -> Option<DragDropPayload<'a>> {
         unsafe {
             let pay = ImGui_GetDragDropPayload();
@@ -4616,3 +6563,302 @@ impl TableColumnSortSpec {
         SortDirection::from_bits(self.0.SortDirection).unwrap_or(SortDirection::None)
     }
 }
+
+/// Sorts `items` as described by the sort specs of a table, usually from
+/// [`Ui::table_with_sort_specs`].
+///
+/// The `cmp` function compares two items by the column with the given index, in ascending order.
+/// Every column in `specs` is used in order, the later ones only to break ties of the former.
+/// The sort is stable, so items equal in every sorted column keep their relative order.
+pub fn table_sort<T>(
+    items: &mut [T],
+    specs: &[TableColumnSortSpec],
+    mut cmp: impl FnMut(usize, &T, &T) -> std::cmp::Ordering,
+) {
+    items.sort_by(|a, b| {
+        for spec in specs {
+            let ord = match spec.sort_direction() {
+                SortDirection::Ascending => cmp(spec.index(), a, b),
+                SortDirection::Descending => cmp(spec.index(), b, a),
+                SortDirection::None => continue,
+            };
+            if ord.is_ne() {
+                return ord;
+            }
+        }
+        std::cmp::Ordering::Equal
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::*;
+
+    #[test]
+    fn input_text_highlight_gets_the_current_text() {
+        let mut h = Harness::new();
+        let seen = RefCell::new(Vec::new());
+        for code in ["fn main() {}", "let x = 1;"] {
+            let mut text = String::from(code);
+            let mut drawn = false;
+            h.frame_with_draw_data(
+                |ui| {
+                    ui.input_text_multiline_config(lbl(c"##code"), &mut text)
+                        .highlight(|t| {
+                            seen.borrow_mut().push(t.to_owned());
+                            vec![(0..2, Color::RED)]
+                        })
+                        .build();
+                },
+                |draw_data| drawn = has_vertex_color(draw_data, Color::RED),
+            );
+            assert!(drawn);
+        }
+        assert_eq!(*seen.borrow(), ["fn main() {}", "let x = 1;"]);
+    }
+
+    #[test]
+    fn fit_font_size_shrinks_long_texts() {
+        let mut h = Harness::new();
+        h.frame(|ui| {
+            let long_text = "A text that is too long to fit";
+            let short = ui.fit_font_size("Hi", 100.0, 40.0);
+            let long = ui.fit_font_size(long_text, 100.0, 40.0);
+            assert_eq!(short, 40.0);
+            assert!(long < short);
+            let width = ui.calc_text_size(long_text).x * long / ui.get_font_size();
+            assert!(width <= 100.001);
+        });
+    }
+
+    #[test]
+    fn drag_vec2_updates_the_components() {
+        let mut h = Harness::new();
+        let mut v = vec2(0.0, 0.0);
+        let mut center = vec2(0.0, 0.0);
+        h.frame(|ui| {
+            let pos = ui.get_cursor_screen_pos();
+            let width = (ui.calc_item_width() - ui.style().ItemInnerSpacing.x) / 2.0;
+            center = pos + vec2(width, ui.get_frame_height()) / 2.0;
+            ui.drag_vec2_config(lbl(c"##v"), &mut v).build();
+        });
+        h.drag(center, center + vec2(30.0, 0.0), 3, |ui| {
+            ui.drag_vec2_config(lbl(c"##v"), &mut v).build();
+        });
+        assert!(v.x > 0.0);
+        assert_eq!(v.y, 0.0);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    fn unbalanced_popup_is_reported() {
+        let mut h = Harness::new();
+        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            h.frame(|ui| {
+                ui.open_popup(id(c"outer"));
+                ui.popup_config(id(c"outer")).with(|| {
+                    ui.open_popup(id(c"inner"));
+                    // Begin the inner popup without ending it
+                    assert!(unsafe { ImGui_BeginPopup(c"inner".as_ptr(), 0) });
+                });
+            });
+        }));
+        let msg = *res.unwrap_err().downcast::<String>().unwrap();
+        assert!(msg.contains("1 popup(s) begun but not ended"), "{msg}");
+        // The popups have been ended, so the following frames work as usual
+        h.frame(|ui| {
+            ui.popup_config(id(c"outer")).with(|| {});
+        });
+    }
+
+    #[test]
+    fn table_sort_uses_every_spec_in_order() {
+        let spec = |index: i32, direction: SortDirection| {
+            let mut spec: ImGuiTableColumnSortSpecs = unsafe { std::mem::zeroed() };
+            spec.ColumnIndex = index;
+            spec.SortDirection = direction.bits();
+            TableColumnSortSpec(spec)
+        };
+        let mut items = [("b", 1), ("a", 2), ("b", 2), ("a", 1), ("c", 1)];
+        let cmp = |column: usize, a: &(&str, i32), b: &(&str, i32)| match column {
+            0 => a.0.cmp(b.0),
+            _ => a.1.cmp(&b.1),
+        };
+        // By the number descending, then by the name
+        let specs = [
+            spec(1, SortDirection::Descending),
+            spec(0, SortDirection::Ascending),
+        ];
+        table_sort(&mut items, &specs, cmp);
+        assert_eq!(items, [("a", 2), ("b", 2), ("a", 1), ("b", 1), ("c", 1)]);
+        // Only by the name: the sort is stable
+        table_sort(&mut items, &specs[1..], cmp);
+        assert_eq!(items, [("a", 2), ("a", 1), ("b", 2), ("b", 1), ("c", 1)]);
+        // A column without direction is ignored
+        table_sort(&mut items, &[spec(0, SortDirection::None)], cmp);
+        assert_eq!(items, [("a", 2), ("a", 1), ("b", 2), ("b", 1), ("c", 1)]);
+    }
+
+    #[test]
+    fn input_text_max_length_truncates() {
+        let mut h = Harness::new();
+        // A text that is already too long is truncated at once
+        let mut text = String::from("123456789");
+        let mut changed = false;
+        let mut center = vec2(0.0, 0.0);
+        h.frame(|ui| {
+            changed = ui
+                .input_text_config(lbl(c"##text"), &mut text)
+                .max_length(5)
+                .build();
+            center = item_center(ui);
+        });
+        assert!(changed);
+        assert_eq!(text, "12345");
+
+        // Typing after the limit is discarded, counting characters, not bytes
+        text.clear();
+        h.click(center, |ui| {
+            ui.input_text_config(lbl(c"##text"), &mut text)
+                .max_length(5)
+                .build();
+        });
+        h.type_text("ñandú€x", |ui| {
+            ui.input_text_config(lbl(c"##text"), &mut text)
+                .max_length(5)
+                .build();
+        });
+        assert_eq!(text, "ñandú");
+    }
+
+    #[test]
+    fn current_font_is_the_pushed_one() {
+        let mut h = Harness::new();
+        h.frame(|ui| {
+            assert_eq!(ui.current_font().FontSize, 13.0);
+            ui.with_font(FontId(1), || {
+                let font = ui.current_font();
+                assert!(std::ptr::eq(font, unsafe { font_ptr(FontId(1)) }));
+                assert_eq!(font.FontSize, 20.0);
+                assert_eq!(ui.get_font_size(), font.FontSize);
+            });
+            assert!(std::ptr::eq(ui.current_font(), unsafe {
+                font_ptr(FontId(0))
+            }));
+        });
+    }
+
+    #[test]
+    fn font_size_is_restored_after_the_scope() {
+        let mut h = Harness::new();
+        h.frame(|ui| {
+            let size = ui.get_font_size();
+            ui.with_font_size(FontId(0), 2.0 * size, || {
+                assert_eq!(ui.get_font_size(), 2.0 * size);
+            });
+            assert_eq!(ui.get_font_size(), size);
+            assert_eq!(ui.current_font().Scale, 1.0);
+        });
+    }
+
+    #[test]
+    fn expand_tabs_to_the_next_stop() {
+        assert!(matches!(
+            expand_tabs("no tabs", 4),
+            Cow::Borrowed("no tabs")
+        ));
+        assert_eq!(expand_tabs("\tx", 4), "    x");
+        assert_eq!(expand_tabs("ab\tx", 4), "ab  x");
+        assert_eq!(expand_tabs("abcd\tx", 4), "abcd    x");
+        // Every line starts at column 0, and characters are counted, not bytes
+        assert_eq!(expand_tabs("a\tb\nñ\tc", 3), "a  b\nñ  c");
+        // A 0 width is taken as 1
+        assert_eq!(expand_tabs("a\tb", 0), "a b");
+    }
+
+    // Two tall child windows side by side, with their scroll synchronized. Returns the scroll of
+    // both
+    fn synced_children<A>(ui: &Ui<A>) -> [f32; 2] {
+        let mut ids = [0; 2];
+        let mut scroll = [0.0; 2];
+        for (i, name) in [c"left", c"right"].into_iter().enumerate() {
+            if i > 0 {
+                ui.same_line();
+            }
+            ui.child_config(lbl(name))
+                .size(vec2(200.0, 200.0))
+                .with(|| {
+                    for _ in 0..100 {
+                        ui.text("line");
+                    }
+                    ids[i] = ui.get_window_id();
+                    scroll[i] = ui.get_scroll_y();
+                });
+        }
+        ui.sync_scroll(&ids);
+        scroll
+    }
+
+    #[test]
+    fn sync_scroll_copies_the_hovered_scroll() {
+        let mut h = Harness::new();
+        let mut center = vec2(0.0, 0.0);
+        h.frame(|ui| {
+            synced_children(ui);
+            center = item_center(ui);
+        });
+        // Scroll the right child with the mouse wheel
+        h.hover(center, |ui| {
+            synced_children(ui);
+        });
+        h.input().add_mouse_wheel_event(vec2(0.0, -1.0));
+        let mut scroll = [0.0; 2];
+        h.frames(3, |ui| scroll = synced_children(ui));
+        assert!(scroll[1] > 0.0);
+        assert_eq!(scroll[0], scroll[1]);
+    }
+
+    // Draws a rectangle in every layer: background, window, modal popup and foreground
+    fn layered_rects<A>(ui: &Ui<A>, open: bool) {
+        let (a, b) = (vec2(10.0, 10.0), vec2(50.0, 50.0));
+        ui.background_draw_list()
+            .add_rect_filled(a, b, Color::RED, 0.0, DrawFlags::None);
+        ui.window_draw_list()
+            .add_rect_filled(a, b, Color::GREEN, 0.0, DrawFlags::None);
+        if open {
+            ui.open_popup(id(c"modal"));
+        }
+        ui.popup_modal_config(lbl(c"modal")).with(|| {
+            ui.window_draw_list()
+                .add_rect_filled(a, b, Color::BLUE, 0.0, DrawFlags::None);
+        });
+        ui.foreground_draw_list()
+            .add_rect_filled(a, b, Color::YELLOW, 0.0, DrawFlags::None);
+    }
+
+    #[test]
+    fn draw_lists_are_layered() {
+        let mut h = Harness::new();
+        h.frame(|ui| layered_rects(ui, true));
+        // The modal popup is hidden in its first frame, while it is measured
+        h.frame(|ui| layered_rects(ui, false));
+        let mut order = Vec::new();
+        h.frame_with_draw_data(
+            |ui| layered_rects(ui, false),
+            |draw_data| {
+                // The index of the draw list that has each color
+                order = [Color::RED, Color::GREEN, Color::BLUE, Color::YELLOW]
+                    .map(|color| {
+                        let col = color.as_u32();
+                        draw_data.CmdLists.iter().position(|&list| unsafe {
+                            (*list).VtxBuffer.iter().any(|v| v.col == col)
+                        })
+                    })
+                    .to_vec();
+            },
+        );
+        let order: Vec<usize> = order.into_iter().map(Option::unwrap).collect();
+        assert!(order.is_sorted_by(|a, b| a < b), "{order:?}");
+    }
+}
@@ -16,6 +16,58 @@ impl<A> Ui<A> {
             &*(ptr as *const Style)
         }
     }
+    /// Calls `f` and then restores the full style to what it was before.
+    ///
+    /// This protects against code, such as third-party C libraries, that leaves the style dirty.
+    pub fn with_style_snapshot<R>(&self, f: impl FnOnce() -> R) -> R {
+        struct RestoreGuard(ImGuiStyle);
+        impl Drop for RestoreGuard {
+            fn drop(&mut self) {
+                unsafe {
+                    *ImGui_GetStyle() = self.0;
+                }
+            }
+        }
+        let _guard = RestoreGuard(unsafe { *ImGui_GetStyle() });
+        f()
+    }
+    /// Gets the current value of a style color, including any pushed color.
+    ///
+    /// The global style alpha is already applied.
+    pub fn style_color(&self, id: ColorId) -> Color {
+        unsafe {
+            let mut c = Color::from(*ImGui_GetStyleColorVec4(id.bits()));
+            c.a *= (*ImGui_GetStyle()).Alpha;
+            c
+        }
+    }
+    /// Gets the current `ItemSpacing`, including any pushed style var.
+    ///
+    /// This and the other getters below are shortcuts for reading the fields of `style()`, that
+    /// is always the live style.
+    pub fn get_item_spacing(&self) -> Vector2 {
+        im_to_v2(self.style().ItemSpacing)
+    }
+    /// Gets the current `ItemInnerSpacing`.
+    pub fn get_item_inner_spacing(&self) -> Vector2 {
+        im_to_v2(self.style().ItemInnerSpacing)
+    }
+    /// Gets the current `FramePadding`.
+    pub fn get_frame_padding(&self) -> Vector2 {
+        im_to_v2(self.style().FramePadding)
+    }
+    /// Gets the current `WindowPadding`.
+    pub fn get_window_padding(&self) -> Vector2 {
+        im_to_v2(self.style().WindowPadding)
+    }
+    /// Gets the current `CellPadding`.
+    pub fn get_cell_padding(&self) -> Vector2 {
+        im_to_v2(self.style().CellPadding)
+    }
+    /// Gets the current `IndentSpacing`.
+    pub fn get_indent_spacing(&self) -> f32 {
+        self.style().IndentSpacing
+    }
 }
 
 /// A wrapper for the `ImGuiStyle` type.
@@ -73,3 +125,31 @@ impl Style {
         c
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::*;
+
+    #[test]
+    fn style_snapshot_reverts_changes() {
+        let mut h = Harness::new();
+        h.frame(|ui| {
+            let rounding = ui.style().FrameRounding;
+            let text = ui.style_color(ColorId::Text);
+            let r = ui.with_style_snapshot(|| {
+                unsafe {
+                    let style = &mut *ImGui_GetStyle();
+                    style.FrameRounding = rounding + 5.0;
+                    style.Colors[ColorId::Text.bits() as usize] = Color::RED.into();
+                }
+                assert_eq!(ui.style().FrameRounding, rounding + 5.0);
+                assert_eq!(ui.style_color(ColorId::Text), Color::RED);
+                42
+            });
+            assert_eq!(r, 42);
+            assert_eq!(ui.style().FrameRounding, rounding);
+            assert_eq!(ui.style_color(ColorId::Text), text);
+        });
+    }
+}
@@ -0,0 +1,1512 @@
+//! Composite widgets, built using only the basic ImGui functions.
+
+use super::*;
+use std::ops::RangeInclusive;
+
+/// The result of [`Ui::split_button`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SplitButtonResult {
+    /// Nothing happened.
+    None,
+    /// The main part of the button was clicked.
+    Clicked,
+    /// The arrow part of the button was clicked, so the menu has been opened.
+    MenuOpened,
+}
+
+impl<A> Ui<A> {
+    /// Shows the visible part of `label` to the right of the previous item, as regular widgets do.
+    fn widget_label(&self, label: &CStr) {
+        unsafe {
+            let end = ImGui_FindRenderedTextEnd(label.as_ptr(), null());
+            if end != label.as_ptr() {
+                ImGui_SameLine(0.0, self.style().ItemInnerSpacing.x);
+                ImGui_TextUnformatted(label.as_ptr(), end);
+            }
+        }
+    }
+
+    /// Shows a 4x4 grid of float inputs, one `input_float_4` per row, useful to edit transformations.
+    ///
+    /// Returns `true` if any value is changed.
+    pub fn input_mat4(&self, label: LblId<impl IntoCStr>, m: &mut [[f32; 4]; 4]) -> bool {
+        let label = label.into();
+        let mut changed = false;
+        unsafe {
+            ImGui_PushID(label.as_ptr());
+        }
+        self.with_group(|| {
+            for (i, row) in m.iter_mut().enumerate() {
+                self.with_push(ItemId(i), || {
+                    changed |= self.input_float_4_config(lbl(c"##row"), row).build();
+                });
+            }
+        });
+        unsafe {
+            ImGui_PopID();
+        }
+        self.widget_label(&label);
+        changed
+    }
+
+    /// Shows a circular dial with a pointer, to edit an angle by dragging around it.
+    ///
+    /// The angle is in radians, clockwise from the positive X axis. When changed it is wrapped
+    /// into the `[0, 2π)` range.
+    ///
+    /// Returns `true` if the angle is changed.
+    pub fn angle_dial(&self, id: impl IntoCStr, radians: &mut f32) -> bool {
+        let radius = self.get_frame_height() * 1.5;
+        let center = self.get_cursor_screen_pos() + vec2(radius, radius);
+        self.invisible_button_config(id)
+            .size(vec2(2.0 * radius, 2.0 * radius))
+            .build();
+        let active = self.is_item_active();
+        let hovered = self.is_item_hovered();
+
+        let mut changed = false;
+        if active {
+            let d = self.get_mouse_pos() - center;
+            // Too close to the center the angle is too unstable
+            if d.x * d.x + d.y * d.y > 4.0 {
+                let angle = d.y.atan2(d.x).rem_euclid(std::f32::consts::TAU);
+                if angle != *radians {
+                    *radians = angle;
+                    changed = true;
+                    unsafe { ImGui_MarkItemEdited(ImGui_GetItemID()) };
+                }
+            }
+        }
+
+        let bg = if active {
+            ColorId::FrameBgActive
+        } else if hovered {
+            ColorId::FrameBgHovered
+        } else {
+            ColorId::FrameBg
+        };
+        let dl = self.window_draw_list();
+        dl.add_circle_filled(center, radius, self.style_color(bg), 0);
+        let tip = center + vec2(radians.cos(), radians.sin()) * (radius - 2.0);
+        dl.add_line(
+            center,
+            tip,
+            self.style_color(ColorId::SliderGrabActive),
+            2.0,
+        );
+        dl.add_circle_filled(center, 3.0, self.style_color(ColorId::SliderGrab), 0);
+        changed
+    }
+
+    /// Shows a row of `max` clickable stars, with the first `value` of them filled.
+    ///
+    /// Hovering the stars previews the new value, clicking sets it.
+    ///
+    /// Returns `true` if the value is changed.
+    pub fn rating(&self, id: impl IntoCStr, value: &mut u32, max: u32) -> bool {
+        if max == 0 {
+            return false;
+        }
+        let size = self.get_frame_height();
+        let pos = self.get_cursor_screen_pos();
+        let clicked = self
+            .invisible_button_config(id)
+            .size(vec2(size * max as f32, size))
+            .build();
+        let hovered = self.is_item_hovered().then(|| {
+            let x = self.get_mouse_pos().x - pos.x;
+            ((x / size).max(0.0) as u32 + 1).min(max)
+        });
+
+        let mut changed = false;
+        if let (true, Some(h)) = (clicked, hovered) {
+            if *value != h {
+                *value = h;
+                changed = true;
+                unsafe { ImGui_MarkItemEdited(ImGui_GetItemID()) };
+            }
+        }
+
+        let (shown, color_on) = match hovered {
+            Some(h) => (h, ColorId::PlotHistogramHovered),
+            None => (*value, ColorId::PlotHistogram),
+        };
+        let color_on = self.style_color(color_on);
+        let color_off = self.style_color(ColorId::TextDisabled);
+        let dl = self.window_draw_list();
+        let r_outer = size * 0.45;
+        let r_inner = r_outer * 0.4;
+        for i in 0..max {
+            let center = pos + vec2((i as f32 + 0.5) * size, 0.5 * size);
+            let points: Vec<ImVec2> = (0..10)
+                .map(|k| {
+                    let r = if k % 2 == 0 { r_outer } else { r_inner };
+                    let a = std::f32::consts::PI * (k as f32 / 5.0 - 0.5);
+                    v2_to_im(center + vec2(a.cos(), a.sin()) * r)
+                })
+                .collect();
+            if i < shown {
+                dl.add_concave_poly_filled(&points, color_on);
+            } else {
+                dl.add_polyline(&points, color_off, DrawFlags::Closed, 1.0);
+            }
+        }
+        changed
+    }
+
+    /// Shows a list of tags as removable chips, followed by an input to add new ones.
+    ///
+    /// The text being typed is kept in `new_tag`, it is added to `tags` when pressing Enter or
+    /// typing a comma. Clicking on a chip removes that tag.
+    ///
+    /// `new_tag` is owned by the caller, as the buffer of [`Ui::input_text_config`] is, because
+    /// a `Ui` lives only for one frame and there is no other place to keep it between frames.
+    /// It also lets the caller inspect the pending text or clear it, for example when the form
+    /// is reset. Use a different `new_tag` for each `tag_input`.
+    ///
+    /// Returns `true` if the list of tags is changed.
+    pub fn tag_input(
+        &self,
+        id: impl IntoCStr,
+        tags: &mut Vec<String>,
+        new_tag: &mut String,
+    ) -> bool {
+        let id = id.into();
+        unsafe { ImGui_PushID(id.as_ptr()) };
+
+        let style = self.style();
+        let line_end_x = self.get_cursor_screen_pos().x + self.get_content_region_avail().x;
+        let mut removed = None;
+        for (i, tag) in tags.iter().enumerate() {
+            let label = format!("{tag} \u{d7}");
+            if i > 0 {
+                let w = self.calc_text_size(&label).x + 2.0 * style.FramePadding.x;
+                self.same_line();
+                if self.get_cursor_screen_pos().x + w > line_end_x {
+                    self.new_line();
+                }
+            }
+            if self.with_push(ItemId(i), || self.small_button(lbl(label))) {
+                removed = Some(i);
+            }
+        }
+        let mut changed = false;
+        if let Some(i) = removed {
+            tags.remove(i);
+            changed = true;
+        }
+
+        if !tags.is_empty() {
+            self.same_line();
+            if line_end_x - self.get_cursor_screen_pos().x < 4.0 * self.get_frame_height() {
+                self.new_line();
+            }
+        }
+        self.set_next_item_width(-f32::MIN_POSITIVE);
+        let enter = self
+            .input_text_config(lbl(c"##new"), new_tag)
+            .flags(InputTextFlags::EnterReturnsTrue)
+            .build();
+        if enter || new_tag.contains(',') {
+            for t in new_tag.split(',') {
+                let t = t.trim();
+                if !t.is_empty() {
+                    tags.push(t.to_owned());
+                    changed = true;
+                }
+            }
+            new_tag.clear();
+            unsafe {
+                // The active input keeps its own copy of the text, so restart it to clear it
+                if self.is_item_active() {
+                    ImGui_ClearActiveID();
+                }
+            }
+            self.set_keyboard_focus_here(-1);
+        }
+        unsafe { ImGui_PopID() };
+        changed
+    }
+
+    /// Shows controls to navigate a paged list: previous and next arrows and numbered pages.
+    ///
+    /// `current_page` is 0-based, but the pages are labeled 1-based. If there are too many pages
+    /// only the first, the last and the ones around the current one are shown, the rest are
+    /// replaced by an ellipsis, so the cost does not depend on `total_pages`.
+    ///
+    /// Returns `true` if the current page is changed.
+    pub fn pagination(
+        &self,
+        id: impl IntoCStr,
+        current_page: &mut usize,
+        total_pages: usize,
+    ) -> bool {
+        if total_pages == 0 {
+            return false;
+        }
+        let id = id.into();
+        unsafe { ImGui_PushID(id.as_ptr()) };
+        let cur = (*current_page).min(total_pages - 1);
+        let mut next = cur;
+
+        self.with_disabled(cur == 0, || {
+            if self.arrow_button(c"##prev", Dir::Left) {
+                next = cur - 1;
+            }
+        });
+        for page in pagination_layout(cur, total_pages) {
+            self.same_line();
+            let Some(page) = page else {
+                self.align_text_to_frame_padding();
+                self.text("...");
+                continue;
+            };
+            let color =
+                (page == cur).then(|| (ColorId::Button, self.style_color(ColorId::ButtonActive)));
+            let clicked = self.with_push(color, || self.button(lbl(format!("{}", page + 1))));
+            if clicked {
+                next = page;
+            }
+        }
+        self.same_line();
+        self.with_disabled(cur + 1 >= total_pages, || {
+            if self.arrow_button(c"##next", Dir::Right) {
+                next = cur + 1;
+            }
+        });
+        unsafe { ImGui_PopID() };
+
+        let changed = next != *current_page;
+        *current_page = next;
+        changed
+    }
+
+    /// Shows an integer value between `-` and `+` buttons that change it by `step`.
+    ///
+    /// The buttons repeat while held. The value is clamped to `range`.
+    ///
+    /// Returns `true` if the value is changed.
+    pub fn stepper_int(
+        &self,
+        label: LblId<impl IntoCStr>,
+        value: &mut i32,
+        step: i32,
+        range: RangeInclusive<i32>,
+    ) -> bool {
+        let label = label.into();
+        let height = self.get_frame_height();
+        let spacing = self.style().ItemInnerSpacing.x;
+        let width = [*range.start(), *range.end()]
+            .iter()
+            .map(|x| self.calc_text_size(&x.to_string()).x)
+            .fold(0.0, f32::max);
+
+        unsafe { ImGui_PushID(label.as_ptr()) };
+        let mut v = *value;
+        let mut text_pos = Vector2::new(0.0, 0.0);
+        self.with_push((ItemFlags::ButtonRepeat, true), || {
+            if self
+                .button_config(lbl(c"-"))
+                .size(vec2(height, height))
+                .build()
+            {
+                v = v.saturating_sub(step);
+            }
+            self.same_line_ex(0.0, spacing);
+            text_pos = self.get_cursor_screen_pos();
+            self.dummy(vec2(width, height));
+            self.same_line_ex(0.0, spacing);
+            if self
+                .button_config(lbl(c"+"))
+                .size(vec2(height, height))
+                .build()
+            {
+                v = v.saturating_add(step);
+            }
+        });
+        unsafe { ImGui_PopID() };
+        let v = v.clamp(*range.start(), *range.end());
+
+        let text = v.to_string();
+        let text_size = self.calc_text_size(&text);
+        let text_pos = text_pos + vec2(width - text_size.x, height - text_size.y) / 2.0;
+        self.window_draw_list()
+            .add_text(text_pos, self.style_color(ColorId::Text), &text);
+        self.widget_label(&label);
+
+        let changed = v != *value;
+        *value = v;
+        changed
+    }
+
+    /// Calls `f` inside a child window with a visible border and window padding.
+    ///
+    /// It is a shortcut for `child_config()` with `ChildFlags::Borders`.
+    pub fn bordered_child<R>(
+        &self,
+        id: LblId<impl IntoCStr>,
+        size: Vector2,
+        f: impl FnOnce() -> R,
+    ) -> Option<R> {
+        self.child_config(id)
+            .size(size)
+            .child_flags(ChildFlags::Borders)
+            .with(f)
+    }
+
+    /// Shows a text followed by a small button that copies it to the clipboard.
+    ///
+    /// Returns `true` if the text is copied.
+    pub fn copyable_value(&self, text: &str) -> bool {
+        self.text(text);
+        self.same_line();
+        let copy = self.with_id(text, || self.small_button(lbl(c"Copy")));
+        if copy {
+            self.set_clipboard_text(text);
+        }
+        copy
+    }
+
+    /// Shows a centered placeholder, for a list or a table without data.
+    ///
+    /// It is composed of an optional image, with its size, a title, a subtitle in disabled color
+    /// and an optional button.
+    ///
+    /// Returns `true` if the button is clicked.
+    pub fn empty_state(
+        &self,
+        icon: Option<(TextureId, Vector2)>,
+        title: &str,
+        subtitle: &str,
+        action: Option<&str>,
+    ) -> bool {
+        let style = self.style();
+        let avail = self.get_content_region_avail();
+        let title_size = self.calc_text_size(title);
+        let subtitle_size = self.calc_text_size(subtitle);
+        let action_size =
+            action.map(|a| self.calc_text_size(a) + 2.0 * im_to_v2(style.FramePadding));
+
+        let spacing = style.ItemSpacing.y;
+        let mut height = title_size.y + spacing + subtitle_size.y;
+        if let Some((_, size)) = icon {
+            height += size.y + spacing;
+        }
+        if let Some(size) = action_size {
+            height += spacing + size.y;
+        }
+
+        let start = self.get_cursor_pos();
+        let center_x = |w: f32| self.set_cursor_pos_x(start.x + ((avail.x - w) / 2.0).max(0.0));
+        self.set_cursor_pos_y(start.y + ((avail.y - height) / 2.0).max(0.0));
+        if let Some((tex, size)) = icon {
+            center_x(size.x);
+            self.image_config(tex, size).build();
+        }
+        center_x(title_size.x);
+        self.text(title);
+        center_x(subtitle_size.x);
+        self.text_disabled(subtitle);
+        match (action, action_size) {
+            (Some(action), Some(size)) => {
+                center_x(size.x);
+                self.button(lbl(action))
+            }
+            _ => false,
+        }
+    }
+
+    /// Shows a button with an attached arrow that opens a popup menu.
+    ///
+    /// The `menu` function is called to build the contents of the popup while it is open,
+    /// usually with a few `menu_item`s.
+    pub fn split_button(
+        &self,
+        label: LblId<impl IntoCStr>,
+        menu: impl FnOnce(),
+    ) -> SplitButtonResult {
+        let label = label.into();
+        unsafe { ImGui_PushID(label.as_ptr()) };
+        let mut res = SplitButtonResult::None;
+        if self.button(lbl(&*label)) {
+            res = SplitButtonResult::Clicked;
+        }
+        let menu_pos = vec2(self.get_item_rect_min().x, self.get_item_rect_max().y);
+        self.same_line_ex(0.0, 0.0);
+        if self.arrow_button(c"##arrow", Dir::Down) {
+            self.open_popup(raw_id(c"##menu"));
+            res = SplitButtonResult::MenuOpened;
+        }
+        self.set_next_window_pos(menu_pos, Cond::Appearing, vec2(0.0, 0.0));
+        self.popup_config(raw_id(c"##menu")).with(menu);
+        unsafe { ImGui_PopID() };
+        res
+    }
+
+    /// Shows a list of collapsing headers where at most one of them is open at a time.
+    ///
+    /// `open` is the index of the open section, or `None` if all are closed. Opening a section
+    /// closes the previously opened one. The `render` function is called with the index of the
+    /// open section to build its contents.
+    ///
+    /// Returns `true` if the open section is changed.
+    pub fn accordion(
+        &self,
+        id: impl Hashable,
+        sections: &[&str],
+        open: &mut Option<usize>,
+        mut render: impl FnMut(usize),
+    ) -> bool {
+        let prev = *open;
+        self.with_id(id, || {
+            for (i, section) in sections.iter().enumerate() {
+                self.set_next_item_open(prev == Some(i), Cond::Always);
+                let is_open = self.with_id(i, || {
+                    self.collapsing_header_config(lbl(*section))
+                        .with(|| render(i))
+                        .is_some()
+                });
+                if is_open && prev != Some(i) {
+                    *open = Some(i);
+                } else if !is_open && prev == Some(i) && *open == prev {
+                    *open = None;
+                }
+            }
+        });
+        *open != prev
+    }
+
+    /// Shows two child windows side by side, separated by a draggable splitter.
+    ///
+    /// `ratio` is the fraction of the available width given to the left pane. It is read and
+    /// written back when the splitter is dragged, so keep it in your application state to
+    /// persist the layout. It is clamped to `[0.05, 0.95]`.
+    ///
+    /// Returns `true` if the ratio is changed.
+    pub fn with_columns_resizable(
+        &self,
+        id: impl Hashable,
+        ratio: &mut f32,
+        left: impl FnOnce(),
+        right: impl FnOnce(),
+    ) -> bool {
+        let prev = *ratio;
+        self.with_id(id, || {
+            let avail = self.get_content_region_avail();
+            let thickness = self.style().ItemSpacing.x.max(4.0);
+            let usable = (avail.x - thickness).max(1.0);
+            *ratio = ratio.clamp(0.05, 0.95);
+
+            self.child_config(lbl(c"##left"))
+                .size(vec2((usable * *ratio).round(), avail.y))
+                .with(left);
+            self.same_line_ex(0.0, 0.0);
+
+            let pos = self.get_cursor_screen_pos();
+            self.invisible_button_config(c"##splitter")
+                .size(vec2(thickness, avail.y))
+                .build();
+            let active = self.is_item_active();
+            let hovered = self.is_item_hovered();
+            if active {
+                let delta = self.io().MouseDelta.x;
+                if delta != 0.0 {
+                    *ratio = (*ratio + delta / usable).clamp(0.05, 0.95);
+                }
+            }
+            if active || hovered {
+                self.set_mouse_cursor(MouseCursor::ResizeEW);
+            }
+            let color = if active {
+                ColorId::SeparatorActive
+            } else if hovered {
+                ColorId::SeparatorHovered
+            } else {
+                ColorId::Separator
+            };
+            let x = pos.x + (thickness / 2.0).floor();
+            self.window_draw_list().add_rect_filled(
+                vec2(x, pos.y),
+                vec2(x + 1.0, pos.y + avail.y),
+                self.style_color(color),
+                0.0,
+                DrawFlags::None,
+            );
+            self.same_line_ex(0.0, 0.0);
+
+            self.child_config(lbl(c"##right"))
+                .size(vec2(0.0, avail.y))
+                .with(right);
+        });
+        *ratio != prev
+    }
+
+    /// Shows an icon glyph followed by a text, in a single line, as a group.
+    ///
+    /// The `icon` should be a character from an icon font merged into the current font, so
+    /// that both share the same line height and baseline.
+    pub fn icon_text(&self, icon: char, text: &str) {
+        let mut buf = [0; 4];
+        let icon = icon.encode_utf8(&mut buf);
+        self.with_group(|| {
+            self.text(icon);
+            self.same_line_ex(0.0, self.style().ItemInnerSpacing.x);
+            self.text(text);
+        });
+    }
+
+    /// Lays out `count` items in a wrapping grid, with as many columns as fit in the available width.
+    ///
+    /// Every column is at least `min_item_width` wide, and they are stretched to fill the whole
+    /// width. The `render` function is called for each item index, inside a group, with an
+    /// `ItemWidth` pushed to the column width and the item index pushed as ID.
+    ///
+    /// Returns the number of columns used.
+    pub fn layout_columns(
+        &self,
+        min_item_width: f32,
+        count: usize,
+        mut render: impl FnMut(usize),
+    ) -> usize {
+        let avail = self.get_content_region_avail().x;
+        let spacing = self.style().ItemSpacing.x;
+        let columns = grid_columns(avail, min_item_width, spacing);
+        let width = ((avail - spacing * (columns - 1) as f32) / columns as f32).max(1.0);
+        let start_x = self.get_cursor_pos().x;
+        for i in 0..count {
+            let col = i % columns;
+            if col != 0 {
+                self.same_line_ex(start_x + col as f32 * (width + spacing), 0.0);
+            }
+            self.with_push((ItemId(i), ItemWidth(width)), || {
+                self.with_group(|| render(i));
+            });
+        }
+        columns
+    }
+
+    /// Shows a card: a rounded, shadowed and bordered container, that can be clicked.
+    ///
+    /// The card is `width` wide, or the available width if it is not positive, and as tall as
+    /// its contents, built by `f`. The ID of the card is pushed while calling `f`.
+    ///
+    /// Returns `true` if the card is clicked. Clicks on interactive items inside the card
+    /// are not reported.
+    pub fn card(&self, id: impl Hashable, width: f32, f: impl FnOnce()) -> bool {
+        let width = if width > 0.0 {
+            width
+        } else {
+            self.get_content_region_avail().x
+        };
+        let padding = self.style().WindowPadding;
+        let rounding = self.style().ChildRounding.max(self.style().FrameRounding);
+        let min = self.get_cursor_screen_pos();
+
+        // The contents are drawn in the channel 1, the background is added later in channel 0.
+        // The splitter is local, not the one of the draw list, so that the cards can be nested.
+        let draw_list = unsafe { ImGui_GetWindowDrawList() };
+        let mut splitter: ImDrawListSplitter = unsafe { std::mem::zeroed() };
+        unsafe {
+            ImDrawListSplitter_Split(&mut splitter, draw_list, 2);
+            ImDrawListSplitter_SetCurrentChannel(&mut splitter, draw_list, 1);
+        }
+        self.with_id(id, || {
+            self.set_cursor_screen_pos(min + vec2(padding.x, padding.y));
+            self.with_push(ItemWidth(width - 2.0 * padding.x), || self.with_group(f));
+        });
+        let max = vec2(min.x + width, self.get_item_rect_max().y + padding.y);
+
+        // An item covering the whole card, to handle the hovering and clicking
+        self.set_cursor_screen_pos(min);
+        self.dummy(max - min);
+        let hovered = self.is_item_hovered();
+        let clicked = self.is_item_clicked(MouseButton::Left);
+
+        unsafe {
+            ImDrawListSplitter_SetCurrentChannel(&mut splitter, draw_list, 0);
+        }
+        let dl = self.window_draw_list();
+        let shadow = Color::new(0.0, 0.0, 0.0, 0.25 * self.style().Alpha);
+        dl.add_rect_filled(
+            min + vec2(2.0, 3.0),
+            max + vec2(2.0, 3.0),
+            shadow,
+            rounding,
+            DrawFlags::None,
+        );
+        dl.add_rect_filled(
+            min,
+            max,
+            self.style_color(ColorId::WindowBg),
+            rounding,
+            DrawFlags::None,
+        );
+        let border = if hovered {
+            ColorId::ButtonHovered
+        } else {
+            ColorId::Border
+        };
+        dl.add_rect(
+            min,
+            max,
+            self.style_color(border),
+            rounding,
+            DrawFlags::None,
+            1.0,
+        );
+        unsafe {
+            ImDrawListSplitter_Merge(&mut splitter, draw_list);
+            ImDrawListSplitter_ClearFreeMemory(&mut splitter);
+        }
+        clicked
+    }
+
+    /// Shows a small line chart of `values`, without axes or labels, such as in a table cell.
+    ///
+    /// The vertical range is adjusted to the minimum and maximum of the values. If the size is
+    /// 0 in any axis, the available width or the frame height are used instead.
+    pub fn sparkline(&self, values: &[f32], size: Vector2, color: Color) {
+        let size = vec2(
+            if size.x > 0.0 {
+                size.x
+            } else {
+                self.get_content_region_avail().x
+            },
+            if size.y > 0.0 {
+                size.y
+            } else {
+                self.get_frame_height()
+            },
+        );
+        self.dummy(size);
+        if !self.is_item_visible() || values.is_empty() {
+            return;
+        }
+        let points = sparkline_points(values, self.get_item_rect_min(), size);
+        self.window_draw_list()
+            .add_polyline(&points, color, DrawFlags::None, 1.0);
+    }
+
+    /// Shows a unified line diff between `old` and `new`.
+    ///
+    /// Removed lines are prefixed with `-` and colored red, added lines are prefixed with `+`
+    /// and colored green. See [`diff_lines`] for details about the algorithm.
+    pub fn text_diff(&self, old: &str, new: &str) {
+        let lines = diff_lines(old, new);
+        let removed = Color::new(0.9, 0.35, 0.35, 1.0);
+        let added = Color::new(0.35, 0.8, 0.35, 1.0);
+        self.list_clipper(lines.len()).with(|i| {
+            let (prefix, line, color) = match lines[i] {
+                DiffLine::Same(line) => (' ', line, None),
+                DiffLine::Removed(line) => ('-', line, Some((ColorId::Text, removed))),
+                DiffLine::Added(line) => ('+', line, Some((ColorId::Text, added))),
+            };
+            self.with_push(color, || self.text(&format!("{prefix} {line}")));
+        });
+    }
+
+    /// Shows a table with one row per item in `rows`, building only the rows that are visible.
+    ///
+    /// It combines a [`ListClipper`] with a scrolling table, so it can handle millions of rows.
+    /// The header row with the `columns` labels is frozen at the top. `render_cell` is called
+    /// with the row item, its index and the column index, for every visible cell; the row index
+    /// is pushed as ID, so the cells can have widgets without ID conflicts.
+    ///
+    /// All the rows should have the same height, that is measured from the first one.
+    pub fn virtual_table<S: IntoCStr, T>(
+        &self,
+        str_id: LblId<S>,
+        columns: &[&str],
+        rows: &[T],
+        mut render_cell: impl FnMut(&T, usize, usize),
+    ) {
+        self.table_config(str_id, columns.len() as i32)
+            .flags(
+                TableFlags::ScrollY
+                    | TableFlags::RowBg
+                    | TableFlags::BordersOuter
+                    | TableFlags::BordersV
+                    | TableFlags::Resizable,
+            )
+            .with(|| {
+                for &column in columns {
+                    self.table_setup_column(column, TableColumnFlags::None, 0.0, 0);
+                }
+                self.table_setup_scroll_freeze(0, 1);
+                self.table_headers_row();
+                self.list_clipper(rows.len()).with(|i| {
+                    self.table_next_row(TableRowFlags::None, 0.0);
+                    self.with_push(ItemId(i), || {
+                        for column in 0..columns.len() {
+                            self.table_set_column_index(column as i32);
+                            render_cell(&rows[i], i, column);
+                        }
+                    });
+                });
+            });
+    }
+
+    /// Calls `f` with the widgets disabled if there is a `reason`, that is shown as a tooltip.
+    ///
+    /// The whole region is in a group, so the tooltip is shown when hovering any part of it,
+    /// even if disabled. With `None` it just calls `f`.
+    pub fn disabled_with_reason<R>(&self, reason: Option<&str>, f: impl FnOnce() -> R) -> R {
+        let Some(reason) = reason else {
+            return f();
+        };
+        let r = self.with_group(|| self.with_disabled(true, f));
+        if self.is_item_hovered_ex(HoveredFlags::ForTooltip | HoveredFlags::AllowWhenDisabled) {
+            self.set_tooltip(reason);
+        }
+        r
+    }
+
+    /// Like `with_disabled`, but with a custom `StyleVar::DisabledAlpha`.
+    ///
+    /// `alpha_mult` multiplies the current alpha of the disabled widgets: `0.0` makes them
+    /// invisible, `1.0` makes them look like enabled.
+    pub fn with_disabled_ex<R>(&self, disabled: bool, alpha_mult: f32, f: impl FnOnce() -> R) -> R {
+        // DisabledAlpha is used in `BeginDisabled`, so it has to be pushed before that
+        self.with_push(
+            (StyleVar::DisabledAlpha, StyleValue::F32(alpha_mult)),
+            || self.with_disabled(disabled, f),
+        )
+    }
+
+    /// Shows a single line of text, truncated with "..." if it does not fit in the available width.
+    ///
+    /// If it is truncated, the full text is shown as a tooltip when hovered.
+    pub fn text_ellipsis(&self, text: &str) {
+        const ELLIPSIS: &str = "...";
+        let avail = self.get_content_region_avail().x;
+        if self.calc_text_size(text).x <= avail {
+            self.text(text);
+            return;
+        }
+        let avail = avail - self.calc_text_size(ELLIPSIS).x;
+        // Binary search of the longest prefix that fits, by char boundaries
+        let bounds: Vec<usize> = text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([text.len()])
+            .collect();
+        let (mut lo, mut hi) = (0, bounds.len() - 1);
+        while lo < hi {
+            let mid = (lo + hi + 1) / 2;
+            if self.calc_text_size(&text[..bounds[mid]]).x <= avail {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        self.text(&format!("{}{ELLIPSIS}", &text[..bounds[lo]]));
+        self.set_item_tooltip(text);
+    }
+
+    /// Shows a separator with a text, suitable for grouping the items of a menu.
+    ///
+    /// `align` is the horizontal position of the text: `0.0` is left, `0.5` is center and
+    /// `1.0` is right. The horizontal padding is removed, so that a left-aligned text lines up
+    /// with the menu items.
+    pub fn menu_separator_text(&self, text: &str, align: f32) {
+        self.with_push(
+            (
+                (StyleVar::SeparatorTextAlign, StyleValue::X(align)),
+                (StyleVar::SeparatorTextPadding, StyleValue::X(0.0)),
+            ),
+            || self.separator_text(text),
+        );
+    }
+
+    /// Shows a read-only text that can be selected and copied to the clipboard.
+    ///
+    /// It is a multiline input text without a frame, as tall as the text and as wide as the
+    /// available width, so it looks almost like a `text`.
+    pub fn text_selectable(&self, id: impl IntoCStr, text: &str) {
+        let mut text = text.to_owned();
+        let height = self.calc_text_size(&text).y;
+        self.with_push(
+            (
+                (ColorId::FrameBg, Color::TRANSPARENT),
+                (StyleVar::FramePadding, StyleValue::Vec2(vec2(0.0, 0.0))),
+            ),
+            || {
+                self.input_text_multiline_config(lbl_id(c"", id), &mut text)
+                    .size(vec2(-f32::MIN_POSITIVE, height))
+                    .flags(InputTextFlags::ReadOnly)
+                    .build();
+            },
+        );
+    }
+
+    /// Shows an image as big as possible in the available space, keeping its aspect ratio, and
+    /// centered.
+    ///
+    /// `aspect` is the width of the image divided by its height.
+    pub fn image_fit(&self, user_texture_id: TextureId, aspect: f32) {
+        let avail = self.get_content_region_avail();
+        if avail.x <= 0.0 || avail.y <= 0.0 || aspect <= 0.0 {
+            return;
+        }
+        let size = if avail.x / avail.y > aspect {
+            vec2(avail.y * aspect, avail.y)
+        } else {
+            vec2(avail.x, avail.x / aspect)
+        };
+        self.set_cursor_pos(self.get_cursor_pos() + (avail - size) / 2.0);
+        self.image_config(user_texture_id, size).build();
+    }
+}
+
+/// Maps the `values` of a sparkline to points in the rectangle at `pos` with `size`.
+///
+/// The values are spread evenly from left to right, the minimum at the bottom and the maximum
+/// at the top. If all values are equal, they are drawn in the middle.
+fn sparkline_points(values: &[f32], pos: Vector2, size: Vector2) -> Vec<ImVec2> {
+    let (lo, hi) = values
+        .iter()
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), &v| {
+            (lo.min(v), hi.max(v))
+        });
+    let range = hi - lo;
+    let step = if values.len() > 1 {
+        size.x / (values.len() - 1) as f32
+    } else {
+        0.0
+    };
+    values
+        .iter()
+        .enumerate()
+        .map(|(i, &v)| {
+            let t = if range > 0.0 { (v - lo) / range } else { 0.5 };
+            im_vec2(pos.x + i as f32 * step, pos.y + (1.0 - t) * size.y)
+        })
+        .collect()
+}
+
+/// Computes the buttons shown by [`Ui::pagination`]: `Some(page)` for a page button, `None` for
+/// an ellipsis.
+///
+/// With up to 7 pages all of them are shown. With more, only the first, the last and the ones
+/// around `cur`.
+fn pagination_layout(cur: usize, total_pages: usize) -> Vec<Option<usize>> {
+    let mut pages = if total_pages <= 7 {
+        (0..total_pages).collect()
+    } else {
+        vec![
+            0,
+            cur.saturating_sub(1),
+            cur,
+            (cur + 1).min(total_pages - 1),
+            total_pages - 1,
+        ]
+    };
+    pages.sort_unstable();
+    pages.dedup();
+    let mut res = Vec::with_capacity(2 * pages.len());
+    for (i, &page) in pages.iter().enumerate() {
+        if i > 0 && pages[i - 1] + 1 != page {
+            res.push(None);
+        }
+        res.push(Some(page));
+    }
+    res
+}
+
+/// Computes how many columns at least `min_item_width` wide, separated by `spacing`, fit in
+/// `avail`. There is always at least one column.
+fn grid_columns(avail: f32, min_item_width: f32, spacing: f32) -> usize {
+    (((avail + spacing) / (min_item_width + spacing)).floor() as usize).max(1)
+}
+
+/// A line in the output of [`diff_lines`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    /// The line is in both texts.
+    Same(&'a str),
+    /// The line is only in the old text.
+    Removed(&'a str),
+    /// The line is only in the new text.
+    Added(&'a str),
+}
+
+/// Computes the line differences between `old` and `new`.
+///
+/// It uses the longest common subsequence of lines, so it takes time and memory proportional
+/// to the product of the number of lines of both texts. When a line is changed, the removed
+/// line comes before the added one.
+pub fn diff_lines<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let (n, m) = (old.len(), new.len());
+    // lcs[i * (m + 1) + j] is the LCS length of `old[i..]` and `new[j..]`
+    let mut lcs = vec![0u32; (n + 1) * (m + 1)];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i * (m + 1) + j] = if old[i] == new[j] {
+                lcs[(i + 1) * (m + 1) + j + 1] + 1
+            } else {
+                lcs[(i + 1) * (m + 1) + j].max(lcs[i * (m + 1) + j + 1])
+            };
+        }
+    }
+    let mut res = Vec::with_capacity(n.max(m));
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            res.push(DiffLine::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[(i + 1) * (m + 1) + j] >= lcs[i * (m + 1) + j + 1] {
+            res.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            res.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+    res.extend(old[i..].iter().map(|l| DiffLine::Removed(l)));
+    res.extend(new[j..].iter().map(|l| DiffLine::Added(l)));
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::harness::*;
+
+    #[test]
+    fn input_mat4_edits_the_right_element() {
+        let mut h = Harness::new();
+        let mut m = [[0.0; 4]; 4];
+        let mut cell = vec2(0.0, 0.0);
+        h.frame(|ui| {
+            // The center of the cell in row 1, column 2
+            let pos = ui.get_cursor_screen_pos();
+            let style = ui.style();
+            let w = (ui.calc_item_width() - 3.0 * style.ItemInnerSpacing.x) / 4.0;
+            let fh = ui.get_frame_height();
+            cell = pos
+                + vec2(
+                    2.5 * w + 2.0 * style.ItemInnerSpacing.x,
+                    fh + style.ItemSpacing.y + fh / 2.0,
+                );
+            ui.input_mat4(lbl(c"##m"), &mut m);
+        });
+        // Clicking selects the whole text, so typing replaces it
+        h.click(cell, |ui| {
+            ui.input_mat4(lbl(c"##m"), &mut m);
+        });
+        let mut changed = false;
+        h.type_text("7", |ui| changed |= ui.input_mat4(lbl(c"##m"), &mut m));
+        assert!(changed);
+        let mut expected = [[0.0; 4]; 4];
+        expected[1][2] = 7.0;
+        assert_eq!(m, expected);
+    }
+
+    #[test]
+    fn angle_dial_follows_the_mouse_and_wraps() {
+        use std::f32::consts::{FRAC_PI_2, TAU};
+        let mut h = Harness::new();
+        let mut angle = 1.0;
+        let mut center = vec2(0.0, 0.0);
+        let mut radius = 0.0;
+        h.frame(|ui| {
+            radius = ui.get_frame_height() * 1.5;
+            center = ui.get_cursor_screen_pos() + vec2(radius, radius);
+            ui.angle_dial(c"dial", &mut angle);
+        });
+        h.hover(center + vec2(0.8 * radius, 0.0), |ui| {
+            ui.angle_dial(c"dial", &mut angle);
+        });
+        h.mouse_button(true);
+        h.frame(|ui| {
+            ui.angle_dial(c"dial", &mut angle);
+        });
+        assert_eq!(angle, 0.0);
+
+        h.mouse_move(center + vec2(0.0, 0.8 * radius));
+        h.frame(|ui| {
+            ui.angle_dial(c"dial", &mut angle);
+        });
+        assert!((angle - FRAC_PI_2).abs() < 1e-4);
+
+        // Just above the positive X axis is almost a full turn
+        h.mouse_move(center + vec2(0.8 * radius, -1.0));
+        h.frame(|ui| {
+            ui.angle_dial(c"dial", &mut angle);
+        });
+        assert!(angle > 6.0 && angle < TAU);
+        h.mouse_button(false);
+    }
+
+    #[test]
+    fn rating_click_sets_the_value() {
+        let mut h = Harness::new();
+        let mut value = 1;
+        let mut third = vec2(0.0, 0.0);
+        h.frame(|ui| {
+            let size = ui.get_frame_height();
+            third = ui.get_cursor_screen_pos() + vec2(2.5 * size, 0.5 * size);
+            ui.rating(c"stars", &mut value, 5);
+        });
+        let mut changed = false;
+        h.click(third, |ui| changed |= ui.rating(c"stars", &mut value, 5));
+        assert!(changed);
+        assert_eq!(value, 3);
+    }
+
+    #[test]
+    fn tag_input_adds_the_tag_on_enter() {
+        let mut h = Harness::new();
+        let mut tags = vec![String::from("first")];
+        let mut new_tag = String::new();
+        let mut input = vec2(0.0, 0.0);
+        h.frame(|ui| {
+            ui.tag_input(c"tags", &mut tags, &mut new_tag);
+            input = item_center(ui);
+        });
+        h.click(input, |ui| {
+            ui.tag_input(c"tags", &mut tags, &mut new_tag);
+        });
+        h.type_text("second", |ui| {
+            ui.tag_input(c"tags", &mut tags, &mut new_tag);
+        });
+        assert_eq!(tags, ["first"]);
+        assert_eq!(new_tag, "second");
+        let mut changed = false;
+        h.press_key(Key::Enter, |ui| {
+            changed |= ui.tag_input(c"tags", &mut tags, &mut new_tag)
+        });
+        assert!(changed);
+        assert_eq!(tags, ["first", "second"]);
+        assert!(new_tag.is_empty());
+    }
+
+    #[test]
+    fn pagination_next_increments_and_clamps() {
+        let mut h = Harness::new();
+        let mut page = 1;
+        let mut next = vec2(0.0, 0.0);
+        h.frame(|ui| {
+            ui.pagination(c"pages", &mut page, 3);
+            // The last item is the "next" arrow
+            next = item_center(ui);
+        });
+        let mut changed = false;
+        h.click(next, |ui| changed |= ui.pagination(c"pages", &mut page, 3));
+        assert!(changed);
+        assert_eq!(page, 2);
+        // In the last page the arrow is disabled
+        let mut changed = false;
+        h.click(next, |ui| changed |= ui.pagination(c"pages", &mut page, 3));
+        assert!(!changed);
+        assert_eq!(page, 2);
+    }
+
+    #[test]
+    fn pagination_layout_with_many_pages() {
+        assert_eq!(
+            pagination_layout(2, 5),
+            [Some(0), Some(1), Some(2), Some(3), Some(4)]
+        );
+        assert_eq!(
+            pagination_layout(0, 10_000_000),
+            [Some(0), Some(1), None, Some(9_999_999)]
+        );
+        assert_eq!(
+            pagination_layout(5000, 10_000_000),
+            [
+                Some(0),
+                None,
+                Some(4999),
+                Some(5000),
+                Some(5001),
+                None,
+                Some(9_999_999)
+            ]
+        );
+        assert_eq!(
+            pagination_layout(9_999_998, 10_000_000),
+            [
+                Some(0),
+                None,
+                Some(9_999_997),
+                Some(9_999_998),
+                Some(9_999_999)
+            ]
+        );
+    }
+
+    // Returns if each one changed, and the center of the "next" arrow of the first one
+    fn two_paginations<A>(ui: &Ui<A>, pages: &mut [usize; 2]) -> ([bool; 2], Vector2) {
+        let a = ui.pagination(c"a", &mut pages[0], 10_000_000);
+        let next_a = item_center(ui);
+        let b = ui.pagination(c"b", &mut pages[1], 10_000_000);
+        ([a, b], next_a)
+    }
+
+    #[test]
+    fn two_paginations_do_not_collide() {
+        let mut h = Harness::new();
+        let mut pages = [5000, 5000];
+        let mut next_a = vec2(0.0, 0.0);
+        h.frame(|ui| next_a = two_paginations(ui, &mut pages).1);
+        let mut changed = [false; 2];
+        h.click(next_a, |ui| changed = two_paginations(ui, &mut pages).0);
+        assert_eq!(changed, [true, false]);
+        assert_eq!(pages, [5001, 5000]);
+    }
+
+    #[test]
+    fn stepper_repeats_while_held() {
+        let mut h = Harness::new();
+        let mut value = 0;
+        let mut plus = vec2(0.0, 0.0);
+        h.frame(|ui| {
+            ui.stepper_int(lbl(c"##q"), &mut value, 1, 0..=100);
+            // Without a visible label the last item is the "+" button
+            plus = item_center(ui);
+        });
+        // One second at 60 FPS, the first click and then the repetitions
+        h.hold(plus, 60, |ui| {
+            ui.stepper_int(lbl(c"##q"), &mut value, 1, 0..=100);
+        });
+        assert!(value > 5, "value = {value}");
+        assert!(value <= 100);
+    }
+
+    #[test]
+    fn bordered_child_has_borders() {
+        let mut h = Harness::new();
+        let mut borders = None;
+        h.frame(|ui| {
+            borders = ui.bordered_child(lbl(c"child"), vec2(100.0, 100.0), || unsafe {
+                let window = &*(*ImGui_GetCurrentContext()).CurrentWindow;
+                window.ChildFlags & ChildFlags::Borders.bits() != 0
+            });
+        });
+        assert_eq!(borders, Some(true));
+    }
+
+    #[test]
+    fn copyable_value_copies_to_the_clipboard() {
+        let mut h = Harness::new();
+        let mut copy = vec2(0.0, 0.0);
+        h.frame(|ui| {
+            ui.copyable_value("0xDEADBEEF");
+            copy = item_center(ui);
+        });
+        let mut copied = false;
+        let mut clipboard = String::new();
+        h.click(copy, |ui| {
+            copied |= ui.copyable_value("0xDEADBEEF");
+            clipboard = ui.get_clipboard_text();
+        });
+        assert!(copied);
+        assert_eq!(clipboard, "0xDEADBEEF");
+    }
+
+    #[test]
+    fn empty_state_reports_the_action() {
+        let mut h = Harness::new();
+        let mut button = vec2(0.0, 0.0);
+        h.frame(|ui| {
+            ui.empty_state(None, "No data", "Nothing to see here", Some("Reload"));
+            button = item_center(ui);
+        });
+        let mut clicked = false;
+        h.click(button, |ui| {
+            clicked |= ui.empty_state(None, "No data", "Nothing to see here", Some("Reload"));
+        });
+        assert!(clicked);
+        // Clicking elsewhere does nothing
+        let mut clicked = false;
+        h.click(vec2(20.0, 20.0), |ui| {
+            clicked |= ui.empty_state(None, "No data", "Nothing to see here", Some("Reload"));
+        });
+        assert!(!clicked);
+    }
+
+    #[test]
+    fn split_button_main_and_arrow() {
+        let mut h = Harness::new();
+        let mut main = vec2(0.0, 0.0);
+        let mut arrow = vec2(0.0, 0.0);
+        h.frame(|ui| {
+            main = ui.get_cursor_screen_pos() + vec2(5.0, ui.get_frame_height() / 2.0);
+            ui.split_button(lbl(c"Save"), || {});
+            // The popup is closed, so the arrow is the last item
+            arrow = item_center(ui);
+        });
+        let mut results = Vec::new();
+        h.click(main, |ui| {
+            results.push(ui.split_button(lbl(c"Save"), || {}))
+        });
+        assert!(results.contains(&SplitButtonResult::Clicked));
+        assert!(!results.contains(&SplitButtonResult::MenuOpened));
+
+        let mut results = Vec::new();
+        let mut menu_built = false;
+        h.click(arrow, |ui| {
+            results.push(ui.split_button(lbl(c"Save"), || menu_built = true));
+        });
+        assert!(results.contains(&SplitButtonResult::MenuOpened));
+        assert!(!results.contains(&SplitButtonResult::Clicked));
+        h.frame(|ui| {
+            ui.split_button(lbl(c"Save"), || menu_built = true);
+        });
+        assert!(menu_built);
+    }
+
+    #[test]
+    fn accordion_opens_one_section_at_a_time() {
+        let mut h = Harness::new();
+        let sections = ["First", "Second"];
+        let mut open = Some(0);
+        let mut second = vec2(0.0, 0.0);
+        h.frame(|ui| {
+            // With empty contents the headers stay in place when opened
+            let pos = ui.get_cursor_screen_pos();
+            let fh = ui.get_frame_height();
+            second = pos + vec2(20.0, fh + ui.style().ItemSpacing.y + fh / 2.0);
+            ui.accordion("acc", &sections, &mut open, |_| {});
+        });
+        let mut changed = false;
+        h.click(second, |ui| {
+            changed |= ui.accordion("acc", &sections, &mut open, |_| {});
+        });
+        assert!(changed);
+        assert_eq!(open, Some(1));
+        let mut rendered = Vec::new();
+        h.frame(|ui| {
+            ui.accordion("acc", &sections, &mut open, |i| rendered.push(i));
+        });
+        assert_eq!(rendered, [1]);
+    }
+
+    #[test]
+    fn columns_resizable_reads_and_writes_the_ratio() {
+        let mut h = Harness::new();
+        let mut ratio = 0.25;
+        let mut left_width = 0.0;
+        let mut usable = 0.0;
+        let mut splitter = vec2(0.0, 0.0);
+        h.frame(|ui| {
+            let pos = ui.get_cursor_screen_pos();
+            let avail = ui.get_content_region_avail();
+            let thickness = ui.style().ItemSpacing.x.max(4.0);
+            usable = avail.x - thickness;
+            ui.with_columns_resizable(
+                "split",
+                &mut ratio,
+                || left_width = ui.get_window_width(),
+                || {},
+            );
+            splitter = pos + vec2(left_width + thickness / 2.0, avail.y / 2.0);
+        });
+        assert_eq!(left_width, (usable * 0.25).round());
+
+        let mut changed = false;
+        h.drag(splitter, splitter + vec2(80.0, 0.0), 4, |ui| {
+            changed |= ui.with_columns_resizable("split", &mut ratio, || {}, || {});
+        });
+        assert!(changed);
+        assert!(
+            (ratio - (0.25 + 80.0 / usable)).abs() < 0.01,
+            "ratio = {ratio}"
+        );
+    }
+
+    #[test]
+    fn icon_text_width() {
+        let mut h = Harness::new();
+        h.frame(|ui| {
+            ui.icon_text('*', "Favorites");
+            let expected = ui.calc_text_size("*").x
+                + ui.style().ItemInnerSpacing.x
+                + ui.calc_text_size("Favorites").x;
+            assert!((ui.get_item_rect_size().x - expected).abs() < 0.01);
+        });
+    }
+
+    #[test]
+    fn grid_columns_fit_in_the_width() {
+        // 3 columns of 100 and 2 spacings of 10 need 320
+        assert_eq!(grid_columns(320.0, 100.0, 10.0), 3);
+        assert_eq!(grid_columns(319.0, 100.0, 10.0), 2);
+        assert_eq!(grid_columns(429.0, 100.0, 10.0), 3);
+        // Too narrow, but there is always a column
+        assert_eq!(grid_columns(50.0, 100.0, 10.0), 1);
+        assert_eq!(grid_columns(0.0, 100.0, 10.0), 1);
+    }
+
+    #[test]
+    fn layout_columns_renders_every_item() {
+        let mut h = Harness::new();
+        h.frame(|ui| {
+            let avail = ui.get_content_region_avail().x;
+            let spacing = ui.style().ItemSpacing.x;
+            let mut rendered = Vec::new();
+            let columns = ui.layout_columns(100.0, 10, |i| {
+                rendered.push(i);
+                ui.button(lbl(c"item"));
+            });
+            assert_eq!(columns, grid_columns(avail, 100.0, spacing));
+            assert!(columns > 1);
+            assert_eq!(rendered, (0..10).collect::<Vec<_>>());
+        });
+    }
+
+    // An outer card with a text and an inner card, returns if they are clicked and the center of
+    // the inner card
+    fn nested_cards<A>(ui: &Ui<A>) -> (bool, bool, Vector2) {
+        let mut inner = (false, vec2(0.0, 0.0));
+        let outer = ui.card("outer", 300.0, || {
+            ui.text("Outer");
+            inner.0 = ui.card("inner", 0.0, || ui.text("Inner"));
+            inner.1 = item_center(ui);
+        });
+        (outer, inner.0, inner.1)
+    }
+
+    #[test]
+    fn card_is_hovered_and_clicked() {
+        let mut h = Harness::new();
+        let mut center = vec2(0.0, 0.0);
+        let mut hovered_color = Color::TRANSPARENT;
+        let mut hovered = true;
+        h.frame_with_draw_data(
+            |ui| {
+                center = nested_cards(ui).2;
+                hovered_color = ui.style_color(ColorId::ButtonHovered);
+            },
+            |draw_data| hovered = has_vertex_color(draw_data, hovered_color),
+        );
+        assert!(!hovered);
+
+        // The hovered border is drawn with the hovered color
+        h.hover(center, |ui| {
+            nested_cards(ui);
+        });
+        h.frame_with_draw_data(
+            |ui| {
+                nested_cards(ui);
+            },
+            |draw_data| hovered = has_vertex_color(draw_data, hovered_color),
+        );
+        assert!(hovered);
+
+        // The inner card is inside the outer one, so both are clicked
+        let mut clicked = (false, false);
+        h.click(center, |ui| {
+            let (outer, inner, _) = nested_cards(ui);
+            clicked.0 |= outer;
+            clicked.1 |= inner;
+        });
+        assert_eq!(clicked, (true, true));
+    }
+
+    #[test]
+    fn sparkline_points_fill_the_rect() {
+        // `ImVec2` is not `PartialEq`
+        let points = |values: &[f32], pos, size| {
+            sparkline_points(values, pos, size)
+                .iter()
+                .map(|&p| im_to_v2(p))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            points(&[1.0, 3.0, 2.0], vec2(10.0, 20.0), vec2(100.0, 40.0)),
+            [vec2(10.0, 60.0), vec2(60.0, 20.0), vec2(110.0, 40.0)]
+        );
+        // Equal values are in the middle
+        assert_eq!(
+            points(&[5.0, 5.0], vec2(0.0, 0.0), vec2(10.0, 10.0)),
+            [vec2(0.0, 5.0), vec2(10.0, 5.0)]
+        );
+        assert_eq!(
+            points(&[5.0], vec2(0.0, 0.0), vec2(10.0, 10.0)),
+            [vec2(0.0, 5.0)]
+        );
+    }
+
+    #[test]
+    fn diff_lines_cases() {
+        use DiffLine::*;
+        // Insert
+        assert_eq!(
+            diff_lines("a\nc", "a\nb\nc"),
+            [Same("a"), Added("b"), Same("c")]
+        );
+        // Delete
+        assert_eq!(
+            diff_lines("a\nb\nc", "a\nc"),
+            [Same("a"), Removed("b"), Same("c")]
+        );
+        // Change, the removed line goes first
+        assert_eq!(
+            diff_lines("a\nb\nc", "a\nx\nc"),
+            [Same("a"), Removed("b"), Added("x"), Same("c")]
+        );
+        // Empty
+        assert!(diff_lines("", "").is_empty());
+        assert_eq!(diff_lines("", "a\nb"), [Added("a"), Added("b")]);
+        assert_eq!(diff_lines("a\nb", ""), [Removed("a"), Removed("b")]);
+        // Identical
+        assert_eq!(diff_lines("a\nb", "a\nb"), [Same("a"), Same("b")]);
+    }
+
+    #[test]
+    fn virtual_table_renders_only_visible_rows() {
+        let mut h = Harness::new();
+        let rows = vec![(); 1_000_000];
+        let mut cells = Vec::new();
+        h.frames(2, |ui| {
+            cells.clear();
+            ui.virtual_table(lbl(c"table"), &["A", "B"], &rows, |_, row, column| {
+                ui.text("cell");
+                cells.push((row, column));
+            });
+        });
+        // The display fits a few dozen rows, every visible row has both columns
+        assert!(!cells.is_empty());
+        assert!(cells.len() < 200, "{} cells rendered", cells.len());
+        assert!(cells.iter().all(|&(row, column)| row < 100 && column < 2));
+        assert_eq!(
+            cells.iter().filter(|&&(_, column)| column == 0).count() * 2,
+            cells.len()
+        );
+    }
+
+    // A button inside `disabled_with_reason`. Returns if it was disabled and the center of the
+    // group
+    fn reasoned_button<A>(ui: &Ui<A>, reason: Option<&str>) -> (bool, Vector2) {
+        let mut disabled = false;
+        ui.disabled_with_reason(reason, || {
+            ui.button(lbl(c"Save"));
+            disabled = unsafe { (*ImGui_GetCurrentContext()).DisabledStackSize > 0 };
+        });
+        (disabled, item_center(ui))
+    }
+
+    #[test]
+    fn disabled_with_reason_shows_a_tooltip() {
+        for reason in [None, Some("Nothing to save")] {
+            let mut h = Harness::new();
+            let mut disabled = false;
+            let mut center = vec2(0.0, 0.0);
+            h.frame(|ui| (disabled, center) = reasoned_button(ui, reason));
+            assert_eq!(disabled, reason.is_some());
+            // The tooltip needs the mouse to stay still for a while
+            h.hover(center, |ui| {
+                reasoned_button(ui, reason);
+            });
+            h.frames(30, |ui| {
+                reasoned_button(ui, reason);
+            });
+            assert_eq!(h.is_window_active(c"##Tooltip_00"), reason.is_some());
+        }
+    }
+}